@@ -1,26 +1,31 @@
+#![allow(dead_code)]
 use std::io::Read;
 use textparse::{
-    components::{AnyChar, Char, Digit, Eos, Items, NonEmpty, Not, Str, While, Whitespace},
-    Parse, Parser, Position, Span,
+    components::{AnyChar, Char, Digit, Items, NonEmpty, Not, Str, Text, While, Whitespace},
+    Dump, Dumper, Parse, Parser, Position, Span, Unparse,
 };
 
 fn main() -> anyhow::Result<()> {
     let mut text = String::new();
     std::io::stdin().read_to_string(&mut text)?;
 
-    let mut parser = Parser::new(&text);
-    if parser.parse::<(JsonValue, Eos)>().is_some() {
-        println!("OK: the input string is a JSON text.");
-    } else {
-        println!("Error: {}", parser.into_parse_error().file_path("<STDIN>"));
+    match textparse::parse_str::<JsonValue>(&text) {
+        Ok(value) if std::env::args().any(|a| a == "--dump-ast") => {
+            print!("{}", value.dump_to_string(&text));
+        }
+        Ok(value) if std::env::args().any(|a| a == "--unparse") => {
+            print!("{}", value.unparse_to_string());
+        }
+        Ok(_) => println!("OK: the input string is a JSON text."),
+        Err(e) => println!("Error: {}", e.file_path("<STDIN>")),
     }
     Ok(())
 }
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 struct JsonValue(WithoutWhitespaces<JsonValueInner>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 #[parse(name = "a JSON value")]
 enum JsonValueInner {
     Null(JsonNull),
@@ -30,30 +35,30 @@ enum JsonValueInner {
     Object(JsonObject),
 }
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 struct JsonNull(Str<'n', 'u', 'l', 'l'>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 #[parse(name = "a JSON string")]
-struct JsonString(Char<'"'>, While<(Not<Char<'"'>>, AnyChar)>, Char<'"'>);
+struct JsonString(Char<'"'>, Text<While<(Not<Char<'"'>>, AnyChar)>>, Char<'"'>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 #[parse(name = "a JSON number")]
-struct JsonNumber(NonEmpty<While<Digit>>);
+struct JsonNumber(Text<NonEmpty<While<Digit>>>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 #[parse(name = "a JSON array")]
 struct JsonArray(Char<'['>, Csv<JsonValue>, Char<']'>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 #[parse(name = "a JSON object")]
 struct JsonObject(Char<'{'>, Csv<JsonObjectItem>, Char<'}'>);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 struct JsonObjectItem(WithoutWhitespaces<JsonString>, Char<':'>, JsonValue);
 
-#[derive(Clone, Span, Parse)]
+#[derive(Clone, Span, Parse, Dump, Unparse)]
 struct Csv<T>(Items<T, Char<','>>);
 
-#[derive(Clone, Span, Parse)]
-struct WithoutWhitespaces<T>(While<Whitespace>, T, While<Whitespace>);
+#[derive(Clone, Span, Parse, Dump, Unparse)]
+struct WithoutWhitespaces<T>(Text<While<Whitespace>>, T, Text<While<Whitespace>>);
@@ -0,0 +1,59 @@
+use crate::{components::Eos, Parse, ParseError, Parser, Position, Rule};
+
+/// Candidates of the items that could come next at a cursor position (cf. [`completions()`]).
+#[derive(Debug, Clone)]
+pub struct Completions {
+    position: Position,
+    items: Vec<Rule>,
+}
+
+impl Completions {
+    /// Returns the position at where the candidate items start.
+    ///
+    /// This can be before the cursor if the cursor is in the middle of an item
+    /// (e.g., `nu|` for `null`).
+    /// In that case, the text between this position and the cursor is the already typed prefix.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the candidate items sorted by their names.
+    pub fn items(&self) -> &[Rule] {
+        &self.items
+    }
+}
+
+/// Returns the items that could come next at `cursor` when parsing the text of `parser` as an item of `T`.
+///
+/// `parser` should be a fresh instance; its settings (e.g., [`Parser::max_depth()`]) are
+/// applied while parsing the text before `cursor`.
+/// The text after `cursor` is ignored.
+/// If `cursor` is beyond the end of the text or is not on a character boundary,
+/// it is moved back to the nearest preceding character boundary.
+/// Only named items (cf. [`Parse::name()`]) are collected, including the nested ones
+/// (e.g., both `a JSON value` and `null`).
+/// Keyword and punctuation candidates can be obtained via [`Rule::literal()`].
+///
+/// If parsing is aborted (e.g., [`ParseErrorKind::TooDeep`](crate::ParseErrorKind::TooDeep)), the error is returned
+/// instead of the candidates collected so far.
+pub fn completions<T: Parse>(
+    mut parser: Parser,
+    cursor: Position,
+) -> Result<Completions, ParseError> {
+    let text = parser.text();
+    let mut end = cursor.get().min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    parser.truncate_text(Position::new(end));
+    let _ = parser.parse::<(T, Eos)>();
+    if parser.is_aborted() {
+        return Err(parser.into_parse_error());
+    }
+    let mut items = parser.attempted_items().collect::<Vec<_>>();
+    items.sort_by_cached_key(|item| item.name());
+    Ok(Completions {
+        position: parser.expected_position(),
+        items,
+    })
+}
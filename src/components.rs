@@ -1,9 +1,16 @@
 //! Basic components.
-use crate::{Parse, Parser, Position, Span};
+use crate::pretty::{Doc, Format, FormatContext};
+use crate::{
+    Dump, Dumper, Parse, Parser, Position, Span, SyntaxEq, SyntaxHash, Unparse, Visit, VisitMut,
+    Visitor, VisitorMut,
+};
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 /// Empty item.
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Empty {
     position: Position,
 }
@@ -16,26 +23,189 @@ impl Parse for Empty {
     }
 }
 
+impl Dump for Empty {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl Unparse for Empty {
+    fn unparse(&self, _writer: &mut dyn Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl Visit for Empty {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for Empty {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl SyntaxEq for Empty {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl SyntaxHash for Empty {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Format for Empty {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::nil()
+    }
+}
+
+/// Trivia (e.g., whitespaces and comments) consumed by [`Parser::skip_trivia()`].
+///
+/// If the trivia rule is not set by [`Parser::trivia()`], this component is always empty.
+#[derive(Debug, Clone, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trivia {
+    start_position: Position,
+    text: String,
+    end_position: Position,
+}
+
+impl Trivia {
+    /// Returns the text of the trivia.
+    pub fn get(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Parse for Trivia {
+    fn parse(parser: &mut Parser) -> Option<Self> {
+        let start_position = parser.current_position();
+        parser.skip_trivia();
+        let end_position = parser.current_position();
+        Some(Self {
+            start_position,
+            text: parser.text()[start_position.get()..end_position.get()].to_owned(),
+            end_position,
+        })
+    }
+}
+
+impl Dump for Trivia {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl Unparse for Trivia {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_str(&self.text)
+    }
+}
+
+impl Visit for Trivia {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for Trivia {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl SyntaxEq for Trivia {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl SyntaxHash for Trivia {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Format for Trivia {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::nil()
+    }
+}
+
 /// Either `A` or `B`.
-#[derive(Debug, Clone, Copy, Span, Parse)]
+#[derive(Debug, Clone, Copy, Span, Parse, Visit, VisitMut, SyntaxEq, SyntaxHash)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Either<A, B> {
     A(A),
     B(B),
 }
 
+impl<A: Dump, B: Dump> Dump for Either<A, B> {
+    fn dump(&self, dumper: &mut Dumper) {
+        match self {
+            Self::A(x) => x.dump(dumper),
+            Self::B(x) => x.dump(dumper),
+        }
+    }
+}
+
+impl<A: Unparse, B: Unparse> Unparse for Either<A, B> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        match self {
+            Self::A(x) => x.unparse(writer),
+            Self::B(x) => x.unparse(writer),
+        }
+    }
+}
+
+impl<A: Format, B: Format> Format for Either<A, B> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        match self {
+            Self::A(x) => x.format(ctx),
+            Self::B(x) => x.format(ctx),
+        }
+    }
+}
+
 /// One of `A`, `B`, or `C`.
-#[derive(Debug, Clone, Copy, Span, Parse)]
+#[derive(Debug, Clone, Copy, Span, Parse, Visit, VisitMut, SyntaxEq, SyntaxHash)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OneOfThree<A, B, C> {
     A(A),
     B(B),
     C(C),
 }
 
+impl<A: Dump, B: Dump, C: Dump> Dump for OneOfThree<A, B, C> {
+    fn dump(&self, dumper: &mut Dumper) {
+        match self {
+            Self::A(x) => x.dump(dumper),
+            Self::B(x) => x.dump(dumper),
+            Self::C(x) => x.dump(dumper),
+        }
+    }
+}
+
+impl<A: Unparse, B: Unparse, C: Unparse> Unparse for OneOfThree<A, B, C> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        match self {
+            Self::A(x) => x.unparse(writer),
+            Self::B(x) => x.unparse(writer),
+            Self::C(x) => x.unparse(writer),
+        }
+    }
+}
+
+impl<A: Format, B: Format, C: Format> Format for OneOfThree<A, B, C> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        match self {
+            Self::A(x) => x.format(ctx),
+            Self::B(x) => x.format(ctx),
+            Self::C(x) => x.format(ctx),
+        }
+    }
+}
+
 /// One of `A`, `B`, `C`, or `D`.
-#[derive(Debug, Clone, Copy, Span, Parse)]
+#[derive(Debug, Clone, Copy, Span, Parse, Visit, VisitMut, SyntaxEq, SyntaxHash)]
 #[allow(missing_docs)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OneOfFour<A, B, C, D> {
     A(A),
     B(B),
@@ -43,8 +213,45 @@ pub enum OneOfFour<A, B, C, D> {
     D(D),
 }
 
+impl<A: Dump, B: Dump, C: Dump, D: Dump> Dump for OneOfFour<A, B, C, D> {
+    fn dump(&self, dumper: &mut Dumper) {
+        match self {
+            Self::A(x) => x.dump(dumper),
+            Self::B(x) => x.dump(dumper),
+            Self::C(x) => x.dump(dumper),
+            Self::D(x) => x.dump(dumper),
+        }
+    }
+}
+
+impl<A: Unparse, B: Unparse, C: Unparse, D: Unparse> Unparse for OneOfFour<A, B, C, D> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        match self {
+            Self::A(x) => x.unparse(writer),
+            Self::B(x) => x.unparse(writer),
+            Self::C(x) => x.unparse(writer),
+            Self::D(x) => x.unparse(writer),
+        }
+    }
+}
+
+impl<A: Format, B: Format, C: Format, D: Format> Format for OneOfFour<A, B, C, D> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        match self {
+            Self::A(x) => x.format(ctx),
+            Self::B(x) => x.format(ctx),
+            Self::C(x) => x.format(ctx),
+            Self::D(x) => x.format(ctx),
+        }
+    }
+}
+
 /// Optional item.
-#[derive(Debug, Clone, Copy, Span)]
+///
+/// If the `serde` feature is enabled, this is serialized in the same way as `Either<T, Empty>`
+/// so that the position of an absent item is kept.
+#[derive(Debug, Clone, Copy, Span, SyntaxEq, SyntaxHash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Maybe<T>(Either<T, Empty>);
 
 impl<T> Maybe<T> {
@@ -64,10 +271,56 @@ impl<T: Parse> Parse for Maybe<T> {
     }
 }
 
+impl<T: Dump> Dump for Maybe<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        if let Some(x) = self.get() {
+            x.dump(dumper);
+        }
+    }
+}
+
+impl<T: Unparse> Unparse for Maybe<T> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        if let Some(x) = self.get() {
+            x.unparse(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Visit> Visit for Maybe<T> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        if let Some(x) = self.get() {
+            visitor.visit(x);
+        }
+    }
+}
+
+impl<T: VisitMut> VisitMut for Maybe<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        if let Either::A(x) = &mut self.0 {
+            visitor.visit_mut(x);
+        }
+    }
+}
+
+impl<T: Format> Format for Maybe<T> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        self.get().map_or_else(Doc::nil, |x| x.format(ctx))
+    }
+}
+
 /// Indicating to continue parsing while `T::parse()` is succeeded.
+///
+/// Trivia between repetitions is skipped (cf. [`Parser::trivia()`]).
+///
+/// Only the span is kept, so use [`Text`] if the matched text is needed.
 #[derive(Debug, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct While<T> {
     start_position: Position,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _phantom: PhantomData<T>,
     end_position: Position,
 }
@@ -75,7 +328,15 @@ pub struct While<T> {
 impl<T: Parse> Parse for While<T> {
     fn parse(parser: &mut Parser) -> Option<Self> {
         let start_position = parser.current_position();
-        while parser.parse::<T>().is_some() {}
+        if parser.parse::<T>().is_some() {
+            while parser
+                .attempt(|parser| {
+                    parser.skip_trivia();
+                    parser.parse::<T>()
+                })
+                .is_some()
+            {}
+        }
         let end_position = parser.current_position();
         Some(Self {
             start_position,
@@ -87,38 +348,101 @@ impl<T: Parse> Parse for While<T> {
 
 impl<T> Clone for While<T> {
     fn clone(&self) -> Self {
-        Self {
-            start_position: self.start_position,
-            _phantom: self._phantom,
-            end_position: self.end_position,
-        }
+        *self
     }
 }
 
 impl<T> Copy for While<T> {}
 
+impl<T: Span> Dump for While<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl<T: 'static> Visit for While<T> {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl<T: 'static> VisitMut for While<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl<T: Span> Format for While<T> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
+}
+
 /// A whitespace (cf. [`char::is_ascii_whitespace()`]).
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Whitespace {
     start_position: Position,
+    value: char,
     end_position: Position,
 }
 
+impl Whitespace {
+    /// Returns the whitespace character.
+    pub fn get(&self) -> char {
+        self.value
+    }
+}
+
 impl Parse for Whitespace {
     fn parse(parser: &mut Parser) -> Option<Self> {
         let start_position = parser.current_position();
         parser
             .read_char()
             .filter(|c| c.is_ascii_whitespace())
-            .map(|_| Self {
+            .map(|value| Self {
                 start_position,
+                value,
                 end_position: parser.current_position(),
             })
     }
 }
 
+impl Dump for Whitespace {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl Unparse for Whitespace {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_char(self.value)
+    }
+}
+
+impl Visit for Whitespace {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for Whitespace {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl SyntaxEq for Whitespace {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl SyntaxHash for Whitespace {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Format for Whitespace {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::nil()
+    }
+}
+
 /// A character.
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnyChar {
     start_position: Position,
     value: char,
@@ -145,8 +469,47 @@ impl Parse for AnyChar {
     }
 }
 
+impl Dump for AnyChar {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl Unparse for AnyChar {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_char(self.value)
+    }
+}
+
+impl Visit for AnyChar {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for AnyChar {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl SyntaxEq for AnyChar {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl SyntaxHash for AnyChar {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl Format for AnyChar {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
+}
+
 /// A specific character.
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Char<const T: char, const NAMED: bool = true> {
     start_position: Position,
     end_position: Position,
@@ -168,10 +531,51 @@ impl<const T: char, const NAMED: bool> Parse for Char<T, NAMED> {
             None
         }
     }
+
+    fn literal() -> Option<fn() -> String> {
+        Some(|| T.to_string())
+    }
+}
+
+impl<const T: char, const NAMED: bool> Dump for Char<T, NAMED> {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl<const T: char, const NAMED: bool> Unparse for Char<T, NAMED> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_char(T)
+    }
+}
+
+impl<const T: char, const NAMED: bool> Visit for Char<T, NAMED> {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl<const T: char, const NAMED: bool> VisitMut for Char<T, NAMED> {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl<const T: char, const NAMED: bool> SyntaxEq for Char<T, NAMED> {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<const T: char, const NAMED: bool> SyntaxHash for Char<T, NAMED> {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<const T: char, const NAMED: bool> Format for Char<T, NAMED> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
 }
 
 /// A specified string (characters).
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Str<
     const C0: char = '\0',
     const C1: char = '\0',
@@ -219,6 +623,10 @@ impl<
     }
 
     fn name() -> Option<fn() -> String> {
+        Self::literal()
+    }
+
+    fn literal() -> Option<fn() -> String> {
         Some(|| {
             let mut s = String::new();
             for c in [C0, C1, C2, C3, C4, C5, C6, C7, C8, C9] {
@@ -232,6 +640,132 @@ impl<
     }
 }
 
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > Dump for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > Unparse for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        for c in [C0, C1, C2, C3, C4, C5, C6, C7, C8, C9] {
+            if c == '\0' {
+                break;
+            }
+            writer.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > Visit for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > VisitMut for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > SyntaxEq for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > SyntaxHash for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<
+        const C0: char,
+        const C1: char,
+        const C2: char,
+        const C3: char,
+        const C4: char,
+        const C5: char,
+        const C6: char,
+        const C7: char,
+        const C8: char,
+        const C9: char,
+    > Format for Str<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9>
+{
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct NonEmptyItems<Item, Delimiter> {
     items: Vec<Item>,
@@ -262,15 +796,80 @@ impl<Item: Parse, Delimiter: Parse> Parse for NonEmptyItems<Item, Delimiter> {
     fn parse(parser: &mut Parser) -> Option<Self> {
         let mut items = vec![parser.parse::<Item>()?];
         let mut delimiters = Vec::new();
-        while let Some(delimiter) = parser.parse::<Delimiter>() {
+        while let Some(delimiter) = parser.attempt(|parser| {
+            parser.skip_trivia();
+            parser.parse::<Delimiter>()
+        }) {
             delimiters.push(delimiter);
+            parser.skip_trivia();
             items.push(parser.parse()?);
         }
         Some(Self { items, delimiters })
     }
 }
 
+impl<Item: Dump, Delimiter: Dump> Dump for NonEmptyItems<Item, Delimiter> {
+    fn dump(&self, dumper: &mut Dumper) {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                self.delimiters[i - 1].dump(dumper);
+            }
+            item.dump(dumper);
+        }
+    }
+}
+
+impl<Item: Unparse, Delimiter: Unparse> Unparse for NonEmptyItems<Item, Delimiter> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                self.delimiters[i - 1].unparse(writer)?;
+            }
+            item.unparse(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Item: Visit, Delimiter: Visit> Visit for NonEmptyItems<Item, Delimiter> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                visitor.visit(&self.delimiters[i - 1]);
+            }
+            visitor.visit(item);
+        }
+    }
+}
+
+impl<Item: VisitMut, Delimiter: VisitMut> VisitMut for NonEmptyItems<Item, Delimiter> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        for (i, item) in self.items.iter_mut().enumerate() {
+            if i > 0 {
+                visitor.visit_mut(&mut self.delimiters[i - 1]);
+            }
+            visitor.visit_mut(item);
+        }
+    }
+}
+
+impl<Item: Format, Delimiter: Format> Format for NonEmptyItems<Item, Delimiter> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                docs.push(ctx.format(&self.delimiters[i - 1]));
+                docs.push(Doc::line());
+            }
+            docs.push(ctx.format(item));
+        }
+        Doc::concat(docs)
+    }
+}
+
 /// Variable length items split by delimiters.
+///
+/// Trivia around delimiters is skipped (cf. [`Parser::trivia()`]).
 #[derive(Debug, Clone, Span, Parse)]
 pub struct Items<Item, Delimiter>(Maybe<NonEmptyItems<Item, Delimiter>>);
 
@@ -286,8 +885,124 @@ impl<Item, Delimiter> Items<Item, Delimiter> {
     }
 }
 
+impl<Item: Dump, Delimiter: Dump> Dump for Items<Item, Delimiter> {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+    }
+}
+
+impl<Item: Unparse, Delimiter: Unparse> Unparse for Items<Item, Delimiter> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)
+    }
+}
+
+impl<Item: Visit, Delimiter: Visit> Visit for Items<Item, Delimiter> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        self.0.walk(visitor);
+    }
+}
+
+impl<Item: VisitMut, Delimiter: VisitMut> VisitMut for Items<Item, Delimiter> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        self.0.walk_mut(visitor);
+    }
+}
+
+impl<Item: SyntaxEq, Delimiter: SyntaxEq> SyntaxEq for Items<Item, Delimiter> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.items().len() == other.items().len()
+            && self.delimiters().len() == other.delimiters().len()
+            && self
+                .items()
+                .iter()
+                .zip(other.items())
+                .all(|(a, b)| a.syntax_eq(b))
+            && self
+                .delimiters()
+                .iter()
+                .zip(other.delimiters())
+                .all(|(a, b)| a.syntax_eq(b))
+    }
+}
+
+impl<Item: SyntaxHash, Delimiter: SyntaxHash> SyntaxHash for Items<Item, Delimiter> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.items().len().hash(state);
+        for (i, item) in self.items().iter().enumerate() {
+            if i > 0 {
+                self.delimiters()[i - 1].syntax_hash(state);
+            }
+            item.syntax_hash(state);
+        }
+    }
+}
+
+/// Items are separated by [`Doc::line()`] after each delimiter.
+impl<Item: Format, Delimiter: Format> Format for Items<Item, Delimiter> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        self.0.format(ctx)
+    }
+}
+
+/// Items are serialized as `{"items": [...], "delimiters": [...]}`.
+///
+/// If there are no items, the position of the (empty) span is also serialized as `"position"`.
+#[cfg(feature = "serde")]
+impl<Item: serde::Serialize, Delimiter: serde::Serialize> serde::Serialize
+    for Items<Item, Delimiter>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Items", 3)?;
+        if let Either::B(empty) = &(self.0).0 {
+            s.serialize_field("position", &empty.position)?;
+        } else {
+            s.skip_field("position")?;
+        }
+        s.serialize_field("items", self.items())?;
+        s.serialize_field("delimiters", self.delimiters())?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Item: serde::Deserialize<'de>, Delimiter: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for Items<Item, Delimiter>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(bound = "Item: serde::Deserialize<'de>, Delimiter: serde::Deserialize<'de>")]
+        struct Repr<Item, Delimiter> {
+            #[serde(default)]
+            position: Option<Position>,
+            items: Vec<Item>,
+            delimiters: Vec<Delimiter>,
+        }
+
+        let repr = Repr::<Item, Delimiter>::deserialize(deserializer)?;
+        if repr.items.is_empty() && repr.delimiters.is_empty() {
+            let position = repr
+                .position
+                .ok_or_else(|| serde::de::Error::missing_field("position"))?;
+            return Ok(Self(Maybe(Either::B(Empty { position }))));
+        }
+        if repr.items.len() != repr.delimiters.len() + 1 {
+            return Err(serde::de::Error::custom(
+                "the number of delimiters must be one less than the number of items",
+            ));
+        }
+        Ok(Self(Maybe(Either::A(NonEmptyItems {
+            items: repr.items,
+            delimiters: repr.delimiters,
+        }))))
+    }
+}
+
 /// Non-empty item.
-#[derive(Debug, Clone, Copy, Span)]
+#[derive(Debug, Clone, Copy, Span, SyntaxEq, SyntaxHash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NonEmpty<T>(T);
 
 impl<T> NonEmpty<T> {
@@ -308,8 +1023,39 @@ impl<T: Parse> Parse for NonEmpty<T> {
     }
 }
 
+impl<T: Dump> Dump for NonEmpty<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+    }
+}
+
+impl<T: Unparse> Unparse for NonEmpty<T> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)
+    }
+}
+
+impl<T: Visit> Visit for NonEmpty<T> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+    }
+}
+
+impl<T: VisitMut> VisitMut for NonEmpty<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+    }
+}
+
+impl<T: Format> Format for NonEmpty<T> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        self.0.format(ctx)
+    }
+}
+
 /// End-Of-String.
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Eos {
     position: Position,
 }
@@ -330,19 +1076,55 @@ impl Parse for Eos {
     }
 }
 
+impl Dump for Eos {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl Unparse for Eos {
+    fn unparse(&self, _writer: &mut dyn Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl Visit for Eos {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for Eos {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl SyntaxEq for Eos {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl SyntaxHash for Eos {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Format for Eos {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::nil()
+    }
+}
+
 /// Not a specified item.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct Not<T> {
     position: Position,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _item: PhantomData<T>,
 }
 
 impl<T> Clone for Not<T> {
     fn clone(&self) -> Self {
-        Self {
-            position: self.position,
-            _item: self._item,
-        }
+        *self
     }
 }
 
@@ -380,11 +1162,50 @@ impl<T: Parse> Parse for Not<T> {
     }
 }
 
+impl<T> Dump for Not<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl<T> Unparse for Not<T> {
+    fn unparse(&self, _writer: &mut dyn Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: 'static> Visit for Not<T> {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl<T: 'static> VisitMut for Not<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+// `Not` consumes no text, so all its items are equal.
+impl<T> SyntaxEq for Not<T> {
+    fn syntax_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> SyntaxHash for Not<T> {
+    fn syntax_hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl<T> Format for Not<T> {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::nil()
+    }
+}
+
 /// A digit.
 #[derive(Debug, Clone, Copy, Span)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Digit<const RADIX: u8 = 10> {
     start_position: Position,
     value: u8,
+    original: char,
     end_position: Position,
 }
 
@@ -398,13 +1219,142 @@ impl<const RADIX: u8> Digit<RADIX> {
 impl<const RADIX: u8> Parse for Digit<RADIX> {
     fn parse(parser: &mut Parser) -> Option<Self> {
         let start_position = parser.current_position();
-        let value = parser
-            .read_char()
-            .and_then(|c| c.to_digit(u32::from(RADIX)))? as u8;
+        let original = parser.read_char()?;
+        let value = original.to_digit(u32::from(RADIX))? as u8;
         Some(Self {
             start_position,
             value,
+            original,
             end_position: parser.current_position(),
         })
     }
 }
+
+impl<const RADIX: u8> Dump for Digit<RADIX> {
+    fn dump(&self, dumper: &mut Dumper) {
+        dumper.leaf(self);
+    }
+}
+
+impl<const RADIX: u8> Unparse for Digit<RADIX> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_char(self.original)
+    }
+}
+
+impl<const RADIX: u8> Visit for Digit<RADIX> {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl<const RADIX: u8> VisitMut for Digit<RADIX> {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+impl<const RADIX: u8> SyntaxEq for Digit<RADIX> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.original == other.original
+    }
+}
+
+impl<const RADIX: u8> SyntaxHash for Digit<RADIX> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.original.hash(state);
+    }
+}
+
+impl<const RADIX: u8> Format for Digit<RADIX> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
+}
+
+/// An item of `T` with its matched text.
+///
+/// This is useful to keep the text of an item that has no content (e.g., [`While`]).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Text<T> {
+    item: T,
+    text: String,
+}
+
+impl<T> Text<T> {
+    /// Returns the item.
+    pub fn get(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns the matched text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl<T: Span> Span for Text<T> {
+    fn start_position(&self) -> Position {
+        self.item.start_position()
+    }
+
+    fn end_position(&self) -> Position {
+        self.item.end_position()
+    }
+}
+
+impl<T: Parse> Parse for Text<T> {
+    fn parse(parser: &mut Parser) -> Option<Self> {
+        let item: T = parser.parse()?;
+        let text = item.text(parser.text()).to_owned();
+        Some(Self { item, text })
+    }
+
+    fn name() -> Option<fn() -> String> {
+        T::name()
+    }
+
+    fn literal() -> Option<fn() -> String> {
+        T::literal()
+    }
+}
+
+impl<T: Dump> Dump for Text<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.item.dump(dumper);
+    }
+}
+
+impl<T> Unparse for Text<T> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        writer.write_str(&self.text)
+    }
+}
+
+impl<T: Visit> Visit for Text<T> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.item);
+    }
+}
+
+impl<T: VisitMut> VisitMut for Text<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.item);
+    }
+}
+
+/// Items are compared by their matched texts.
+impl<T> SyntaxEq for Text<T> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl<T> SyntaxHash for Text<T> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+    }
+}
+
+impl<T: Span> Format for Text<T> {
+    fn format(&self, _ctx: &FormatContext) -> Doc {
+        Doc::text(self.text.clone())
+    }
+}
@@ -0,0 +1,266 @@
+//! Lossless concrete syntax tree.
+//!
+//! A [`SyntaxTree`] is recorded during parsing
+//! if [`Parser::record_syntax_tree()`](crate::Parser::record_syntax_tree) is enabled.
+//! Every successfully parsed named rule (cf. [`Parse::name()`](crate::Parse::name)) becomes a node,
+//! and the text that is not covered by any named rule (e.g., whitespaces) becomes token nodes.
+//! So the tree covers each byte of the text exactly once.
+use crate::{Position, Rule, Span};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A node recorded during parsing.
+///
+/// The children are shared by the memo entries that produced them.
+#[derive(Debug, Clone)]
+pub(crate) struct GreenNode {
+    rule: Rule,
+    span: Range<Position>,
+    children: Rc<[GreenNode]>,
+}
+
+impl GreenNode {
+    pub(crate) fn new(rule: Rule, span: Range<Position>, children: Vec<GreenNode>) -> Self {
+        Self {
+            rule,
+            span,
+            children: children.into(),
+        }
+    }
+}
+
+/// Kind of [`SyntaxNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    /// The root node that covers the whole text.
+    Root,
+
+    /// A node of a named rule.
+    Rule(Rule),
+
+    /// A token that is not covered by any named rule.
+    Token,
+}
+
+/// Lossless concrete syntax tree.
+///
+/// [`std::fmt::Display`] renders the tree as the original text.
+#[derive(Debug, Clone)]
+pub struct SyntaxTree {
+    text: String,
+    nodes: Vec<NodeData>,
+}
+
+impl SyntaxTree {
+    pub(crate) fn new(text: &str, nodes: &[GreenNode]) -> Self {
+        let mut this = Self {
+            text: text.to_owned(),
+            nodes: Vec::new(),
+        };
+        let span = Position::new(0)..Position::new(text.len());
+        this.add_node(SyntaxKind::Root, span, None, nodes);
+        this
+    }
+
+    /// Returns the text of this tree.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the root node.
+    pub fn root(&self) -> SyntaxNode<'_> {
+        SyntaxNode {
+            tree: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the innermost node that covers the byte at `position`.
+    ///
+    /// The result is always a token unless `position` is out of the text.
+    pub fn node_at(&self, position: Position) -> SyntaxNode<'_> {
+        let mut node = self.root();
+        while let Some(child) = node
+            .children()
+            .find(|c| c.start_position() <= position && position < c.end_position())
+        {
+            node = child;
+        }
+        node
+    }
+
+    fn add_node(
+        &mut self,
+        kind: SyntaxKind,
+        span: Range<Position>,
+        parent: Option<usize>,
+        children: &[GreenNode],
+    ) -> usize {
+        let index = self.nodes.len();
+        let index_in_parent = parent.map_or(0, |p| self.nodes[p].children.len());
+        self.nodes.push(NodeData {
+            kind,
+            span: span.clone(),
+            parent,
+            index_in_parent,
+            children: Vec::new(),
+        });
+        if let Some(parent) = parent {
+            self.nodes[parent].children.push(index);
+        }
+
+        let mut offset = span.start;
+        for child in children {
+            if child.span.start < offset {
+                continue;
+            }
+            if offset < child.span.start {
+                self.add_node(
+                    SyntaxKind::Token,
+                    offset..child.span.start,
+                    Some(index),
+                    &[],
+                );
+            }
+            self.add_node(
+                SyntaxKind::Rule(child.rule),
+                child.span.clone(),
+                Some(index),
+                &child.children,
+            );
+            offset = child.span.end;
+        }
+        if offset < span.end && kind != SyntaxKind::Token {
+            self.add_node(SyntaxKind::Token, offset..span.end, Some(index), &[]);
+        }
+        index
+    }
+}
+
+impl std::fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for node in self.root().descendants() {
+            if node.is_token() {
+                write!(f, "{}", node.source_text())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NodeData {
+    kind: SyntaxKind,
+    span: Range<Position>,
+    parent: Option<usize>,
+    index_in_parent: usize,
+    children: Vec<usize>,
+}
+
+/// A node of [`SyntaxTree`].
+#[derive(Debug, Clone, Copy)]
+pub struct SyntaxNode<'a> {
+    tree: &'a SyntaxTree,
+    index: usize,
+}
+
+impl<'a> SyntaxNode<'a> {
+    /// Returns the kind of this node.
+    pub fn kind(self) -> SyntaxKind {
+        self.data().kind
+    }
+
+    /// Returns the rule of this node if the kind is [`SyntaxKind::Rule`].
+    pub fn rule(self) -> Option<Rule> {
+        if let SyntaxKind::Rule(rule) = self.kind() {
+            Some(rule)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if this node is a token, otherwise `false`.
+    pub fn is_token(self) -> bool {
+        self.kind() == SyntaxKind::Token
+    }
+
+    /// Returns the text covered by this node.
+    pub fn source_text(self) -> &'a str {
+        self.text(&self.tree.text)
+    }
+
+    /// Returns the parent node.
+    pub fn parent(self) -> Option<Self> {
+        self.data().parent.map(|index| self.node(index))
+    }
+
+    /// Returns the child nodes.
+    pub fn children(self) -> impl 'a + Iterator<Item = SyntaxNode<'a>> {
+        let tree = self.tree;
+        self.data()
+            .children
+            .iter()
+            .map(move |&index| SyntaxNode { tree, index })
+    }
+
+    /// Returns the first child node.
+    pub fn first_child(self) -> Option<Self> {
+        self.data().children.first().map(|&index| self.node(index))
+    }
+
+    /// Returns the last child node.
+    pub fn last_child(self) -> Option<Self> {
+        self.data().children.last().map(|&index| self.node(index))
+    }
+
+    /// Returns the next sibling node.
+    pub fn next_sibling(self) -> Option<Self> {
+        let siblings = &self.parent()?.data().children;
+        siblings
+            .get(self.data().index_in_parent + 1)
+            .map(|&index| self.node(index))
+    }
+
+    /// Returns the previous sibling node.
+    pub fn prev_sibling(self) -> Option<Self> {
+        let siblings = &self.parent()?.data().children;
+        let i = self.data().index_in_parent.checked_sub(1)?;
+        Some(self.node(siblings[i]))
+    }
+
+    /// Returns the ancestor nodes, starting from the parent.
+    pub fn ancestors(self) -> impl 'a + Iterator<Item = SyntaxNode<'a>> {
+        std::iter::successors(self.parent(), |node| node.parent())
+    }
+
+    /// Returns this node and its descendant nodes in pre-order.
+    pub fn descendants(self) -> impl 'a + Iterator<Item = SyntaxNode<'a>> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.data().children.iter().rev().map(|&i| node.node(i)));
+            Some(node)
+        })
+    }
+
+    fn node(self, index: usize) -> Self {
+        Self {
+            tree: self.tree,
+            index,
+        }
+    }
+
+    fn data(self) -> &'a NodeData {
+        &self.tree.nodes[self.index]
+    }
+}
+
+impl Span for SyntaxNode<'_> {
+    fn start_position(&self) -> Position {
+        self.data().span.start
+    }
+
+    fn end_position(&self) -> Position {
+        self.data().span.end
+    }
+}
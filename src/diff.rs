@@ -0,0 +1,457 @@
+//! Structural diff between two syntax trees.
+//!
+//! The nodes of the trees (cf. [`SyntaxTree`]) are compared by their rules and whitespace-normalized texts,
+//! so whitespace-only differences (including named whitespace trivia nodes) are not reported.
+use crate::cst::{SyntaxNode, SyntaxTree};
+use crate::span::LineIndex;
+use crate::{Position, Rule, Span};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A change between the old and new trees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A node that exists only in the new tree.
+    Inserted {
+        /// The rule of the node.
+        rule: Rule,
+
+        /// The span of the node in the new text.
+        new: Range<Position>,
+    },
+
+    /// A node that exists only in the old tree.
+    Deleted {
+        /// The rule of the node.
+        rule: Rule,
+
+        /// The span of the node in the old text.
+        old: Range<Position>,
+    },
+
+    /// A node that was moved to another place among its siblings without modification.
+    Moved {
+        /// The rule of the node.
+        rule: Rule,
+
+        /// The span of the node in the old text.
+        old: Range<Position>,
+
+        /// The span of the node in the new text.
+        new: Range<Position>,
+    },
+
+    /// A node whose text was changed.
+    ///
+    /// If the changes are limited to some child nodes, those children are reported instead of this node.
+    Changed {
+        /// The rule of the node.
+        rule: Rule,
+
+        /// The span of the node in the old text.
+        old: Range<Position>,
+
+        /// The span of the node in the new text.
+        new: Range<Position>,
+    },
+}
+
+impl Change {
+    /// Returns the rule of the changed node.
+    pub fn rule(&self) -> Rule {
+        match self {
+            Self::Inserted { rule, .. }
+            | Self::Deleted { rule, .. }
+            | Self::Moved { rule, .. }
+            | Self::Changed { rule, .. } => *rule,
+        }
+    }
+
+    /// Returns the span of the node in the old text.
+    pub fn old_span(&self) -> Option<Range<Position>> {
+        match self {
+            Self::Inserted { .. } => None,
+            Self::Deleted { old, .. } | Self::Moved { old, .. } | Self::Changed { old, .. } => {
+                Some(old.clone())
+            }
+        }
+    }
+
+    /// Returns the span of the node in the new text.
+    pub fn new_span(&self) -> Option<Range<Position>> {
+        match self {
+            Self::Deleted { .. } => None,
+            Self::Inserted { new, .. } | Self::Moved { new, .. } | Self::Changed { new, .. } => {
+                Some(new.clone())
+            }
+        }
+    }
+}
+
+/// Structural diff between two syntax trees.
+///
+/// [`std::fmt::Display`] renders a human-readable report, one change per line.
+#[derive(Debug, Clone)]
+pub struct SyntaxDiff<'a> {
+    old: &'a SyntaxTree,
+    new: &'a SyntaxTree,
+    changes: Vec<Change>,
+}
+
+impl<'a> SyntaxDiff<'a> {
+    /// Computes the diff between `old` and `new`.
+    ///
+    /// Both trees are expected to be parsed with the same root type.
+    /// The child nodes of each node are matched by the longest common subsequence,
+    /// where punctuation nodes (e.g., delimiters) weigh less than the other nodes.
+    /// Then, each remaining node is reported as moved if an identical sibling exists on the other side,
+    /// or as changed if it is paired with the most similar sibling of the same rule,
+    /// otherwise as deleted or inserted.
+    /// Note that moves are only detected among siblings, and punctuation is never reported as moved.
+    /// Nodes consisting only of whitespaces are ignored.
+    pub fn new(old: &'a SyntaxTree, new: &'a SyntaxTree) -> Self {
+        let mut changes = Vec::new();
+        diff_children(old.root(), new.root(), &mut changes);
+        Self { old, new, changes }
+    }
+
+    /// Returns the changes.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns `true` if there are no changes, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl std::fmt::Display for SyntaxDiff<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let old = Side::new(self.old.text());
+        let new = Side::new(self.new.text());
+        for change in &self.changes {
+            let name = change.rule().short_type_name();
+            match change {
+                Change::Inserted { new: span, .. } => {
+                    writeln!(
+                        f,
+                        "inserted {name} {}: {}",
+                        new.span(span),
+                        new.excerpt(span)
+                    )?;
+                }
+                Change::Deleted { old: span, .. } => {
+                    writeln!(
+                        f,
+                        "deleted {name} {}: {}",
+                        old.span(span),
+                        old.excerpt(span)
+                    )?;
+                }
+                Change::Moved {
+                    old: old_span,
+                    new: new_span,
+                    ..
+                } => {
+                    writeln!(
+                        f,
+                        "moved {name} {} -> {}: {}",
+                        old.span(old_span),
+                        new.span(new_span),
+                        new.excerpt(new_span)
+                    )?;
+                }
+                Change::Changed {
+                    old: old_span,
+                    new: new_span,
+                    ..
+                } => {
+                    writeln!(
+                        f,
+                        "changed {name} {} -> {}: {} -> {}",
+                        old.span(old_span),
+                        new.span(new_span),
+                        old.excerpt(old_span),
+                        new.excerpt(new_span)
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Side<'a> {
+    text: &'a str,
+    lines: LineIndex<'a>,
+}
+
+impl<'a> Side<'a> {
+    const MAX_EXCERPT_CHARS: usize = 32;
+
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            lines: LineIndex::new(text),
+        }
+    }
+
+    fn span(&self, span: &Range<Position>) -> String {
+        let (start_line, start_column) = self.lines.line_and_column(span.start);
+        let (end_line, end_column) = self.lines.line_and_column(span.end);
+        format!("{start_line}:{start_column}..{end_line}:{end_column}")
+    }
+
+    fn excerpt(&self, span: &Range<Position>) -> String {
+        let text = span
+            .text(self.text)
+            .lines()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut excerpt = text
+            .chars()
+            .take(Self::MAX_EXCERPT_CHARS)
+            .collect::<String>();
+        let truncated = excerpt.len() < text.len();
+        excerpt = format!("{excerpt:?}");
+        if truncated {
+            excerpt.push_str("...");
+        }
+        excerpt
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry<'a> {
+    node: SyntaxNode<'a>,
+    rule: Rule,
+    key: Vec<&'a str>,
+}
+
+impl<'a> Entry<'a> {
+    fn new(node: SyntaxNode<'a>, rule: Rule) -> Self {
+        let mut key = Vec::new();
+        normalized_text(node, &mut key);
+        Self { node, rule, key }
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        self.rule == other.rule && self.key == other.key
+    }
+
+    fn is_punctuation(&self) -> bool {
+        self.key
+            .iter()
+            .all(|word| !word.chars().any(char::is_alphanumeric))
+    }
+
+    /// Returns the weight of this node in the longest common subsequence.
+    fn weight(&self) -> usize {
+        if self.is_punctuation() {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns the ratio of the common tokens between `self` and `other` (from `0.0` to `1.0`).
+    fn similarity(&self, other: &Self) -> f64 {
+        let mut counts = HashMap::<&str, usize>::new();
+        for word in &self.key {
+            *counts.entry(word).or_default() += 1;
+        }
+        let mut common = 0;
+        for word in &other.key {
+            if let Some(n) = counts.get_mut(word).filter(|n| **n > 0) {
+                *n -= 1;
+                common += 1;
+            }
+        }
+        let total = self.key.len() + other.key.len();
+        if total == 0 {
+            1.0
+        } else {
+            (2 * common) as f64 / total as f64
+        }
+    }
+
+    fn span(&self) -> Range<Position> {
+        self.node.start_position()..self.node.end_position()
+    }
+}
+
+/// Collects the texts of the tokens in `node` without the surrounding whitespaces.
+///
+/// The whitespaces within a token (e.g., in a string literal) are kept because they are likely significant.
+fn normalized_text<'a>(node: SyntaxNode<'a>, words: &mut Vec<&'a str>) {
+    for token in node.descendants().filter(|n| n.is_token()) {
+        let text = token.source_text().trim();
+        if !text.is_empty() {
+            words.push(text);
+        }
+    }
+}
+
+fn rule_children(node: SyntaxNode<'_>) -> Vec<Entry<'_>> {
+    node.children()
+        .filter_map(|c| c.rule().map(|rule| Entry::new(c, rule)))
+        .filter(|entry| !entry.key.is_empty())
+        .collect()
+}
+
+fn own_tokens(node: SyntaxNode<'_>) -> Vec<&str> {
+    node.children()
+        .filter(|c| c.is_token())
+        .map(|c| c.source_text().trim())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn diff_children(old: SyntaxNode, new: SyntaxNode, changes: &mut Vec<Change>) {
+    let olds = rule_children(old);
+    let news = rule_children(new);
+
+    let mut old_matched = vec![false; olds.len()];
+    let mut new_matched = vec![None; news.len()];
+    for (i, j) in longest_common_subsequence(&olds, &news) {
+        old_matched[i] = true;
+        new_matched[j] = Some((i, Match::Same));
+    }
+
+    for (i, o) in olds.iter().enumerate() {
+        if old_matched[i] {
+            continue;
+        }
+        if let Some(j) = (0..news.len()).find(|&j| new_matched[j].is_none() && o.same_as(&news[j]))
+        {
+            // Moves of punctuation or among identical siblings are not meaningful.
+            let unique = olds.iter().filter(|x| x.same_as(o)).count() == 1
+                && news.iter().filter(|x| x.same_as(o)).count() == 1
+                && !o.is_punctuation();
+            old_matched[i] = true;
+            new_matched[j] = Some((i, if unique { Match::Moved } else { Match::Same }));
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for (i, o) in olds.iter().enumerate().filter(|(i, _)| !old_matched[*i]) {
+        for (j, n) in news.iter().enumerate() {
+            if new_matched[j].is_none() && o.rule == n.rule {
+                candidates.push((o.similarity(n), i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+    for (_, i, j) in candidates {
+        if !old_matched[i] && new_matched[j].is_none() {
+            old_matched[i] = true;
+            new_matched[j] = Some((i, Match::Paired));
+        }
+    }
+
+    // Deleted nodes are reported just before the next node aligned with the old tree.
+    let mut next_old = 0;
+    for (j, n) in news.iter().enumerate() {
+        match new_matched[j] {
+            Some((i, Match::Same)) => {
+                report_deleted(&olds, &old_matched, &mut next_old, i, changes);
+            }
+            Some((i, Match::Moved)) => changes.push(Change::Moved {
+                rule: n.rule,
+                old: olds[i].span(),
+                new: n.span(),
+            }),
+            Some((i, Match::Paired)) => {
+                report_deleted(&olds, &old_matched, &mut next_old, i, changes);
+                diff_nodes(&olds[i], n, changes);
+            }
+            None => {
+                let next_aligned = new_matched[j..]
+                    .iter()
+                    .find_map(|m| match m {
+                        Some((i, Match::Same | Match::Paired)) => Some(*i),
+                        _ => None,
+                    })
+                    .unwrap_or(olds.len());
+                report_deleted(&olds, &old_matched, &mut next_old, next_aligned, changes);
+                changes.push(Change::Inserted {
+                    rule: n.rule,
+                    new: n.span(),
+                });
+            }
+        }
+    }
+    report_deleted(&olds, &old_matched, &mut next_old, olds.len(), changes);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Match {
+    Same,
+    Moved,
+    Paired,
+}
+
+fn report_deleted(
+    olds: &[Entry],
+    old_matched: &[bool],
+    next: &mut usize,
+    until: usize,
+    changes: &mut Vec<Change>,
+) {
+    for i in *next..until {
+        if !old_matched[i] {
+            changes.push(Change::Deleted {
+                rule: olds[i].rule,
+                old: olds[i].span(),
+            });
+        }
+    }
+    *next = (*next).max(until);
+}
+
+fn diff_nodes(old: &Entry, new: &Entry, changes: &mut Vec<Change>) {
+    let len = changes.len();
+    diff_children(old.node, new.node, changes);
+    if changes.len() == len || own_tokens(old.node) != own_tokens(new.node) {
+        changes.insert(
+            len,
+            Change::Changed {
+                rule: new.rule,
+                old: old.span(),
+                new: new.span(),
+            },
+        );
+    }
+}
+
+fn longest_common_subsequence(olds: &[Entry], news: &[Entry]) -> Vec<(usize, usize)> {
+    let mut table = vec![vec![0; news.len() + 1]; olds.len() + 1];
+    for i in (0..olds.len()).rev() {
+        for j in (0..news.len()).rev() {
+            let skip = table[i + 1][j].max(table[i][j + 1]);
+            table[i][j] = if olds[i].same_as(&news[j]) {
+                skip.max(table[i + 1][j + 1] + olds[i].weight())
+            } else {
+                skip
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < olds.len() && j < news.len() {
+        if olds[i].same_as(&news[j]) && table[i][j] == table[i + 1][j + 1] + olds[i].weight() {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
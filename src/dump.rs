@@ -0,0 +1,156 @@
+use crate::parse::short_type_name;
+use crate::span::LineIndex;
+use crate::{Position, Span};
+use std::fmt::Write;
+
+pub use textparse_derive::Dump;
+
+/// This trait allows for rendering a parsed item as an indented tree.
+///
+/// Each node is rendered as a line consisting of the type name, the span (`line:col..line:col`),
+/// and an excerpt of the source text.
+pub trait Dump: Span {
+    /// Writes this item (and its children) to `dumper`.
+    ///
+    /// Wrapper components (e.g., [`Maybe`](crate::components::Maybe) and tuples)
+    /// do not make nodes by themselves and just dump their contents.
+    fn dump(&self, dumper: &mut Dumper);
+
+    /// Renders this item as a tree.
+    fn dump_to_string(&self, text: &str) -> String {
+        let mut dumper = Dumper::new(text);
+        self.dump(&mut dumper);
+        dumper.finish()
+    }
+}
+
+/// Tree printer used by [`Dump`].
+#[derive(Debug)]
+pub struct Dumper<'a> {
+    text: &'a str,
+    lines: LineIndex<'a>,
+    max_excerpt_chars: usize,
+    depth: usize,
+    output: String,
+}
+
+impl<'a> Dumper<'a> {
+    /// Makes a new [`Dumper`] instance.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            lines: LineIndex::new(text),
+            max_excerpt_chars: 32,
+            depth: 0,
+            output: String::new(),
+        }
+    }
+
+    /// Sets the maximum number of characters of an excerpt.
+    ///
+    /// Longer excerpts are truncated and end with `...`.
+    /// The default value is `32`.
+    pub fn max_excerpt_chars(mut self, n: usize) -> Self {
+        self.max_excerpt_chars = n;
+        self
+    }
+
+    /// Writes a node for `item` and then its children written by `children`.
+    pub fn node<T: ?Sized + Span, F>(&mut self, item: &T, children: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.write_line(std::any::type_name::<T>(), item);
+        self.depth += 1;
+        children(self);
+        self.depth -= 1;
+    }
+
+    /// Writes a node for `item` that has no children.
+    pub fn leaf<T: ?Sized + Span>(&mut self, item: &T) {
+        self.node(item, |_| {});
+    }
+
+    /// Returns the rendered tree.
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    fn write_line<T: ?Sized + Span>(&mut self, type_name: &str, item: &T) {
+        let location = |position: Position| {
+            let (line, column) = self.lines.line_and_column(position);
+            format!("{line}:{column}")
+        };
+        let text = item.text(self.text);
+        let mut excerpt = text
+            .chars()
+            .take(self.max_excerpt_chars)
+            .collect::<String>();
+        let truncated = excerpt.len() < text.len();
+        excerpt = format!("{excerpt:?}");
+        if truncated {
+            excerpt.push_str("...");
+        }
+        let _ = writeln!(
+            self.output,
+            "{:indent$}{} {}..{} {}",
+            "",
+            short_type_name(type_name),
+            location(item.start_position()),
+            location(item.end_position()),
+            excerpt,
+            indent = self.depth * 2
+        );
+    }
+}
+
+impl<T: Dump> Dump for Box<T> {
+    fn dump(&self, dumper: &mut Dumper) {
+        (**self).dump(dumper);
+    }
+}
+
+impl<T0: Dump, T1: Dump> Dump for (T0, T1) {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+        self.1.dump(dumper);
+    }
+}
+
+impl<T0: Dump, T1: Dump, T2: Dump> Dump for (T0, T1, T2) {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+        self.1.dump(dumper);
+        self.2.dump(dumper);
+    }
+}
+
+impl<T0: Dump, T1: Dump, T2: Dump, T3: Dump> Dump for (T0, T1, T2, T3) {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+        self.1.dump(dumper);
+        self.2.dump(dumper);
+        self.3.dump(dumper);
+    }
+}
+
+impl<T0: Dump, T1: Dump, T2: Dump, T3: Dump, T4: Dump> Dump for (T0, T1, T2, T3, T4) {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+        self.1.dump(dumper);
+        self.2.dump(dumper);
+        self.3.dump(dumper);
+        self.4.dump(dumper);
+    }
+}
+
+impl<T0: Dump, T1: Dump, T2: Dump, T3: Dump, T4: Dump, T5: Dump> Dump for (T0, T1, T2, T3, T4, T5) {
+    fn dump(&self, dumper: &mut Dumper) {
+        self.0.dump(dumper);
+        self.1.dump(dumper);
+        self.2.dump(dumper);
+        self.3.dump(dumper);
+        self.4.dump(dumper);
+        self.5.dump(dumper);
+    }
+}
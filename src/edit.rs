@@ -0,0 +1,335 @@
+use crate::{Position, Span};
+use std::ops::Range;
+
+/// An edit of a text range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    range: Range<Position>,
+    text: String,
+}
+
+impl TextEdit {
+    /// Makes an edit that replaces the text of `item` with `text`.
+    pub fn replace<T: Span>(item: &T, text: impl Into<String>) -> Self {
+        Self {
+            range: item.start_position()..item.end_position(),
+            text: text.into(),
+        }
+    }
+
+    /// Makes an edit that inserts `text` just before `item`.
+    pub fn insert_before<T: Span>(item: &T, text: impl Into<String>) -> Self {
+        let position = item.start_position();
+        Self {
+            range: position..position,
+            text: text.into(),
+        }
+    }
+
+    /// Makes an edit that inserts `text` just after `item`.
+    pub fn insert_after<T: Span>(item: &T, text: impl Into<String>) -> Self {
+        let position = item.end_position();
+        Self {
+            range: position..position,
+            text: text.into(),
+        }
+    }
+
+    /// Makes an edit that deletes the text of `item`.
+    pub fn delete<T: Span>(item: &T) -> Self {
+        Self::replace(item, "")
+    }
+
+    /// Returns the text to be inserted in place of the range.
+    pub fn new_text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Span for TextEdit {
+    fn start_position(&self) -> Position {
+        self.range.start
+    }
+
+    fn end_position(&self) -> Position {
+        self.range.end
+    }
+}
+
+/// A set of [`TextEdit`]s that are applied at once.
+#[derive(Debug, Default, Clone)]
+pub struct EditSet {
+    edits: Vec<TextEdit>,
+}
+
+impl EditSet {
+    /// Makes a new empty [`EditSet`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an edit.
+    ///
+    /// Multiple insertions at the same position are applied in the order they were added.
+    pub fn push(&mut self, edit: TextEdit) -> &mut Self {
+        self.edits.push(edit);
+        self
+    }
+
+    /// Returns the edits in this set.
+    pub fn edits(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    /// Returns the number of edits in this set.
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Returns `true` if this set has no edits, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Applies the edits to `text` and returns the edited text with a [`PositionMapper`].
+    ///
+    /// This fails if the edits overlap with each other or if an edit range is not within `text`.
+    /// Note that an insertion at the boundary of a replaced range does not overlap with the replacement.
+    pub fn apply(&self, text: &str) -> Result<(String, PositionMapper), EditError> {
+        let mut edits = self.edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|e| (e.range.start, e.range.end));
+
+        for edit in &edits {
+            let Range { start, end } = edit.range;
+            if start > end
+                || end.get() > text.len()
+                || !text.is_char_boundary(start.get())
+                || !text.is_char_boundary(end.get())
+            {
+                return Err(EditError::OutOfText {
+                    range: edit.range.clone(),
+                });
+            }
+        }
+        for pair in edits.windows(2) {
+            let (first, second) = (&pair[0].range, &pair[1].range);
+            if second.start < first.end {
+                return Err(EditError::Overlapping {
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut mapped = Vec::with_capacity(edits.len());
+        let mut offset = 0;
+        for edit in edits {
+            output.push_str(&text[offset..edit.range.start.get()]);
+            let new_start = Position::new(output.len());
+            output.push_str(&edit.text);
+            mapped.push(MappedEdit {
+                old: edit.range.clone(),
+                new: new_start..Position::new(output.len()),
+            });
+            offset = edit.range.end.get();
+        }
+        output.push_str(&text[offset..]);
+        Ok((output, PositionMapper { edits: mapped }))
+    }
+}
+
+impl FromIterator<TextEdit> for EditSet {
+    fn from_iter<I: IntoIterator<Item = TextEdit>>(iter: I) -> Self {
+        Self {
+            edits: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Mapper from positions in a text before applying an [`EditSet`] to positions in the edited text.
+#[derive(Debug, Clone)]
+pub struct PositionMapper {
+    edits: Vec<MappedEdit>,
+}
+
+impl PositionMapper {
+    /// Maps `position` in the original text to the corresponding position in the edited text.
+    ///
+    /// A position at an insertion point is mapped to after the inserted text,
+    /// and a position within a replaced range is mapped to the start of the replacement.
+    pub fn map(&self, position: Position) -> Position {
+        self.map_with_bias(position, Bias::Right)
+    }
+
+    /// Maps the span of `item` in the original text to the corresponding range in the edited text.
+    ///
+    /// The text inserted just before or after `item` is not included in the resulting range.
+    /// If `item` itself was replaced, the range of the replacement is returned.
+    pub fn map_span<T: Span>(&self, item: &T) -> Range<Position> {
+        let start = self.map_with_bias(item.start_position(), Bias::Right);
+        let end = self.map_with_bias(item.end_position(), Bias::Left);
+        start..end.max(start)
+    }
+
+    fn map_with_bias(&self, position: Position, bias: Bias) -> Position {
+        let mut delta = 0isize;
+        for edit in &self.edits {
+            if edit.old.end < position {
+                delta += edit.delta();
+                continue;
+            }
+            if position < edit.old.start {
+                break;
+            }
+            if edit.old.is_empty() {
+                if bias == Bias::Left {
+                    break;
+                }
+                delta += edit.delta();
+            } else if position == edit.old.start {
+                return edit.new.start;
+            } else if position == edit.old.end && bias == Bias::Right {
+                delta += edit.delta();
+            } else if bias == Bias::Right {
+                return edit.new.start;
+            } else {
+                return edit.new.end;
+            }
+        }
+        Position::new(position.get().wrapping_add_signed(delta))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MappedEdit {
+    old: Range<Position>,
+    new: Range<Position>,
+}
+
+impl MappedEdit {
+    fn delta(&self) -> isize {
+        self.new.len() as isize - self.old.len() as isize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bias {
+    Left,
+    Right,
+}
+
+/// Error of [`EditSet::apply()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EditError {
+    /// Two edits overlap with each other.
+    Overlapping {
+        /// The range of the preceding edit.
+        first: Range<Position>,
+
+        /// The range of the following edit.
+        second: Range<Position>,
+    },
+
+    /// An edit range is out of the text or is not on character boundaries.
+    OutOfText {
+        /// The range of the edit.
+        range: Range<Position>,
+    },
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overlapping { first, second } => write!(
+                f,
+                "overlapping edits: {}..{} and {}..{}",
+                first.start.get(),
+                first.end.get(),
+                second.start.get(),
+                second.end.get()
+            ),
+            Self::OutOfText { range } => write!(
+                f,
+                "edit range {}..{} is out of the text",
+                range.start.get(),
+                range.end.get()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(offset: usize) -> Position {
+        Position::new(offset)
+    }
+
+    fn mapper(text: &str, edits: impl IntoIterator<Item = TextEdit>) -> (String, PositionMapper) {
+        edits
+            .into_iter()
+            .collect::<EditSet>()
+            .apply(text)
+            .expect("valid edits")
+    }
+
+    #[test]
+    fn map_with_bias_around_insertion_and_replacement() {
+        // "hello world" => "hello, rust"
+        let (text, mapper) = mapper(
+            "hello world",
+            [
+                TextEdit::replace(&(p(6)..p(11)), "rust"),
+                TextEdit::insert_before(&p(5), ","),
+            ],
+        );
+        assert_eq!(text, "hello, rust");
+
+        let cases = [
+            // (position, left, right)
+            (0, 0, 0),
+            (5, 5, 6),    // Insertion point.
+            (6, 7, 7),    // Start of the replaced range.
+            (8, 11, 7),   // Within the replaced range.
+            (11, 11, 11), // End of the replaced range.
+        ];
+        for (position, left, right) in cases {
+            assert_eq!(mapper.map_with_bias(p(position), Bias::Left), p(left));
+            assert_eq!(mapper.map_with_bias(p(position), Bias::Right), p(right));
+        }
+        assert_eq!(mapper.map(p(5)), p(6));
+    }
+
+    #[test]
+    fn map_with_bias_after_deletion() {
+        // "abcdef" => "abef"
+        let (text, mapper) = mapper("abcdef", [TextEdit::delete(&(p(2)..p(4)))]);
+        assert_eq!(text, "abef");
+        for bias in [Bias::Left, Bias::Right] {
+            assert_eq!(mapper.map_with_bias(p(1), bias), p(1));
+            assert_eq!(mapper.map_with_bias(p(3), bias), p(2));
+            assert_eq!(mapper.map_with_bias(p(4), bias), p(2));
+            assert_eq!(mapper.map_with_bias(p(5), bias), p(3));
+        }
+    }
+
+    #[test]
+    fn map_span_excludes_adjacent_insertions() {
+        // "ab" => "[ab]"
+        let (_, mapper) = mapper(
+            "ab",
+            [
+                TextEdit::insert_before(&p(0), "["),
+                TextEdit::insert_after(&p(2), "]"),
+            ],
+        );
+        assert_eq!(mapper.map_span(&(p(0)..p(2))), p(1)..p(3));
+        // An empty span at an insertion point is mapped to after the inserted text.
+        assert_eq!(mapper.map_span(&(p(2)..p(2))), p(4)..p(4));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+pub(crate) fn html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes `s` so that it can be embedded in a JSON string.
+pub(crate) fn json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes `s` so that it can be embedded in a double-quoted DOT string used as a label.
+///
+/// DOT has no escape sequences for arbitrary characters (and `\r` and `\l` have special meanings in labels),
+/// so control characters are written as character entities, which Graphviz decodes.
+pub(crate) fn dot(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '&' => escaped.push_str("&amp;"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "&#{};", c as u32);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_escapes_are_valid_in_labels() {
+        assert_eq!(dot("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        assert_eq!(dot("\r\t\u{1b}&"), "&#13;&#9;&#27;&amp;");
+    }
+
+    #[test]
+    fn json_escapes_control_characters() {
+        assert_eq!(json("a\"b\\c\n\u{1b}"), "a\\\"b\\\\c\\n\\u001b");
+    }
+}
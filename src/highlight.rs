@@ -0,0 +1,153 @@
+//! Syntax highlighting driven by parsed item types.
+use crate::{escape, Parse, Parser, Position, Span};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// Highlighter that classifies text ranges based on the types of the parsed items.
+#[derive(Debug, Default, Clone)]
+pub struct Highlighter {
+    classes: HashMap<TypeId, String>,
+}
+
+impl Highlighter {
+    /// Makes a new [`Highlighter`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the items of `T` to the highlight class `class`.
+    pub fn class<T: Parse>(mut self, class: impl Into<String>) -> Self {
+        self.classes.insert(TypeId::of::<T>(), class.into());
+        self
+    }
+
+    /// Classifies the text parsed by `parser`.
+    ///
+    /// Only the items that make up the parse result are classified (cf. [`Parser::result_items()`]).
+    /// If items of mapped types are nested, the innermost one takes priority.
+    /// The resulting highlights are sorted and do not overlap.
+    pub fn highlight(&self, parser: &Parser) -> Vec<Highlight> {
+        let entries = parser
+            .result_items()
+            .into_iter()
+            .filter_map(|item| {
+                let class = self.classes.get(&item.rule().type_id())?;
+                (!item.is_empty()).then_some((item.start_position()..item.end_position(), class))
+            })
+            .collect::<Vec<_>>();
+
+        // Paints each byte with the innermost item that covers it.
+        // As inner items come after outer ones, the later item wins if the spans are the same.
+        let mut painted: Vec<Option<usize>> = vec![None; parser.text().len()];
+        for (i, (span, _)) in entries.iter().enumerate() {
+            for p in &mut painted[span.start.get()..span.end.get()] {
+                if p.is_none_or(|j| entries[j].0.len() >= span.len()) {
+                    *p = Some(i);
+                }
+            }
+        }
+
+        let mut highlights = Vec::<Highlight>::new();
+        let mut prev = None;
+        for (offset, p) in painted.into_iter().enumerate() {
+            if p.is_some() && p == prev {
+                if let Some(last) = highlights.last_mut() {
+                    last.end_position = Position::new(offset + 1);
+                }
+            } else if let Some(i) = p {
+                highlights.push(Highlight {
+                    start_position: Position::new(offset),
+                    end_position: Position::new(offset + 1),
+                    class: entries[i].1.clone(),
+                });
+            }
+            prev = p;
+        }
+        highlights
+    }
+}
+
+/// A classified text range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Highlight {
+    start_position: Position,
+    end_position: Position,
+    class: String,
+}
+
+impl Highlight {
+    /// Returns the highlight class of this range.
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+}
+
+impl Span for Highlight {
+    fn start_position(&self) -> Position {
+        self.start_position
+    }
+
+    fn end_position(&self) -> Position {
+        self.end_position
+    }
+}
+
+/// Renders `text` as HTML where each highlighted range is wrapped by `<span class="CLASS">`.
+///
+/// `highlights` must be sorted and must not overlap (cf. [`Highlighter::highlight()`]).
+pub fn render_html(text: &str, highlights: &[Highlight]) -> String {
+    let mut s = String::new();
+    render(text, highlights, |segment, class| {
+        if let Some(class) = class {
+            s.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                escape::html(class),
+                escape::html(segment)
+            ));
+        } else {
+            s.push_str(&escape::html(segment));
+        }
+    });
+    s
+}
+
+/// Renders `text` with ANSI escape sequences.
+///
+/// `style` returns the escape sequence (e.g., `"\x1b[32m"`) for a highlight class,
+/// or `None` if the class should not be styled.
+///
+/// `highlights` must be sorted and must not overlap (cf. [`Highlighter::highlight()`]).
+pub fn render_ansi<'a, F>(text: &str, highlights: &'a [Highlight], style: F) -> String
+where
+    F: Fn(&'a str) -> Option<&'a str>,
+{
+    let mut s = String::new();
+    render(text, highlights, |segment, class| {
+        if let Some(style) = class.and_then(&style) {
+            s.push_str(style);
+            s.push_str(segment);
+            s.push_str("\x1b[0m");
+        } else {
+            s.push_str(segment);
+        }
+    });
+    s
+}
+
+fn render<'a, F>(text: &str, highlights: &'a [Highlight], mut f: F)
+where
+    F: FnMut(&str, Option<&'a str>),
+{
+    let mut offset = 0;
+    for highlight in highlights {
+        let start = highlight.start_position.get();
+        if offset < start {
+            f(&text[offset..start], None);
+        }
+        f(highlight.text(text), Some(&highlight.class));
+        offset = highlight.end_position.get();
+    }
+    if offset < text.len() {
+        f(&text[offset..], None);
+    }
+}
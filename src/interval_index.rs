@@ -0,0 +1,128 @@
+use crate::Position;
+use std::ops::Range;
+
+/// Static interval tree over spans sorted by their start positions.
+#[derive(Debug)]
+pub(crate) struct IntervalIndex {
+    spans: Vec<Range<Position>>,
+    max_ends: Vec<Position>,
+}
+
+impl IntervalIndex {
+    /// Makes a new index.
+    ///
+    /// `spans` must be sorted by their start positions.
+    pub(crate) fn new(spans: Vec<Range<Position>>) -> Self {
+        let mut this = Self {
+            max_ends: vec![Position::default(); spans.len()],
+            spans,
+        };
+        this.build(0, this.spans.len());
+        this
+    }
+
+    /// Returns the spans that overlap with `range`.
+    ///
+    /// Empty spans are regarded as overlapping if their positions are within `range`.
+    pub(crate) fn query(&self, range: &Range<Position>) -> Vec<Range<Position>> {
+        let mut result = Vec::new();
+        self.query_inner(0, self.spans.len(), range, &mut result);
+        result
+    }
+
+    fn build(&mut self, lo: usize, hi: usize) -> Position {
+        if lo >= hi {
+            return Position::default();
+        }
+        let mid = (lo + hi) / 2;
+        let max_end = self.spans[mid]
+            .end
+            .max(self.build(lo, mid))
+            .max(self.build(mid + 1, hi));
+        self.max_ends[mid] = max_end;
+        max_end
+    }
+
+    fn query_inner(
+        &self,
+        lo: usize,
+        hi: usize,
+        range: &Range<Position>,
+        result: &mut Vec<Range<Position>>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if self.max_ends[mid] < range.start {
+            return;
+        }
+        self.query_inner(lo, mid, range, result);
+
+        let span = &self.spans[mid];
+        if span.start >= range.end {
+            return;
+        }
+        let overlaps = if span.start == span.end {
+            range.start <= span.start
+        } else {
+            range.start < span.end
+        };
+        if overlaps {
+            result.push(span.clone());
+        }
+        self.query_inner(mid + 1, hi, range, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize) -> Range<Position> {
+        Position::new(start)..Position::new(end)
+    }
+
+    fn query(index: &IntervalIndex, start: usize, end: usize) -> Vec<Range<Position>> {
+        let mut spans = index.query(&span(start, end));
+        spans.sort_by_key(|s| (s.start, s.end));
+        spans
+    }
+
+    #[test]
+    fn query_returns_overlapping_spans() {
+        let index = IntervalIndex::new(vec![span(0, 10), span(1, 2), span(3, 5), span(6, 9)]);
+        assert_eq!(query(&index, 0, 1), [span(0, 10)]);
+        assert_eq!(query(&index, 4, 7), [span(0, 10), span(3, 5), span(6, 9)]);
+        assert_eq!(query(&index, 5, 6), [span(0, 10)]);
+        assert_eq!(query(&index, 10, 20), []);
+    }
+
+    #[test]
+    fn query_finds_long_spans_on_the_left_subtree() {
+        let index = IntervalIndex::new(vec![
+            span(0, 100),
+            span(1, 2),
+            span(2, 3),
+            span(3, 4),
+            span(4, 5),
+            span(5, 6),
+            span(6, 7),
+        ]);
+        assert_eq!(query(&index, 50, 51), [span(0, 100)]);
+    }
+
+    #[test]
+    fn query_includes_empty_spans_within_range() {
+        let index = IntervalIndex::new(vec![span(0, 0), span(2, 2), span(2, 4), span(4, 4)]);
+        assert_eq!(query(&index, 2, 3), [span(2, 2), span(2, 4)]);
+        assert_eq!(query(&index, 4, 5), [span(4, 4)]);
+        assert_eq!(query(&index, 1, 2), []);
+    }
+
+    #[test]
+    fn query_on_empty_index() {
+        let index = IntervalIndex::new(Vec::new());
+        assert_eq!(query(&index, 0, 10), []);
+    }
+}
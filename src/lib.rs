@@ -1,9 +1,42 @@
 //! A library to declaratively implement parsers that are based on Packrat Parsing.
+//!
+//! If the `serde` feature is enabled, [`Position`] and the types in [`components`]
+//! implement `serde::Serialize` and `serde::Deserialize`.
 #![warn(missing_docs)]
 pub mod components;
+pub mod cst;
+pub mod diff;
+pub mod highlight;
+pub mod pretty;
 
+mod completion;
+mod dump;
+mod edit;
+mod escape;
+mod interval_index;
+mod mapped_text;
+mod memo_dump;
+mod observer;
 mod parse;
+mod profiler;
 mod span;
+mod syntax_eq;
+mod trace_event;
+mod unparse;
+mod visit;
 
-pub use self::parse::{Parse, ParseError, Parser};
-pub use self::span::{Position, Span};
+pub use self::completion::{completions, Completions};
+pub use self::dump::{Dump, Dumper};
+pub use self::edit::{EditError, EditSet, PositionMapper, TextEdit};
+pub use self::mapped_text::MappedText;
+pub use self::observer::{ParseObserver, TracePrinter};
+pub use self::parse::{
+    parse_file, parse_prefix, parse_str, Checkpoint, MemoEntry, Parse, ParseError, ParseErrorKind,
+    ParseFileError, ParsedItem, Parser, Rule,
+};
+pub use self::profiler::{ProfileReport, Profiler, RuleStats};
+pub use self::span::{LineIndex, Position, Span};
+pub use self::syntax_eq::{SyntaxEq, SyntaxHash};
+pub use self::trace_event::TraceEventRecorder;
+pub use self::unparse::Unparse;
+pub use self::visit::{Visit, VisitMut, Visitor, VisitorMut};
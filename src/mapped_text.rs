@@ -0,0 +1,144 @@
+use crate::{Position, Span};
+use std::ops::Range;
+
+/// A text derived from an original text (e.g., by line splicing or macro expansion)
+/// together with the mapping from its positions back to the original positions.
+///
+/// [`Parser`](crate::Parser) can parse the derived text via [`MappedText::text()`].
+#[derive(Debug, Default, Clone)]
+pub struct MappedText {
+    original: String,
+    text: String,
+    segments: Vec<Segment>,
+}
+
+impl MappedText {
+    /// Makes a new empty [`MappedText`] instance derived from `original`.
+    pub fn new<T: Into<String>>(original: T) -> Self {
+        Self {
+            original: original.into(),
+            text: String::new(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Returns the original text.
+    pub fn original_text(&self) -> &str {
+        &self.original
+    }
+
+    /// Returns the derived text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Appends the `range` of the original text to the derived text as-is.
+    pub fn push_original(&mut self, range: Range<Position>) {
+        if range.is_empty() {
+            return;
+        }
+        self.segments.push(Segment {
+            start: self.text.len(),
+            origin: Origin::Original(range.start),
+        });
+        self.text
+            .push_str(&self.original[range.start.get()..range.end.get()]);
+    }
+
+    /// Appends a generated text (e.g., the result of a macro expansion) to the derived text.
+    ///
+    /// `expansion_chain` is the list of the original ranges that caused the expansion.
+    /// It is ordered from innermost to outermost, so the last element is usually
+    /// the macro invocation written by the user.
+    pub fn push_expansion(&mut self, text: &str, expansion_chain: &[Range<Position>]) {
+        assert!(!expansion_chain.is_empty());
+        if text.is_empty() {
+            return;
+        }
+        self.segments.push(Segment {
+            start: self.text.len(),
+            origin: Origin::Expansion(expansion_chain.to_owned()),
+        });
+        self.text.push_str(text);
+    }
+
+    /// Translates a position in the derived text into the corresponding position in the original text.
+    ///
+    /// Positions in expanded text are mapped to the start of the outermost expansion.
+    pub fn original_position(&self, position: Position) -> Position {
+        let Some((i, segment)) = self.segment(position) else {
+            return position;
+        };
+        match &segment.origin {
+            Origin::Original(start) => Position::new(start.get() + position.get() - segment.start),
+            Origin::Expansion(chain) => {
+                let outermost = &chain[chain.len() - 1];
+                if position.get() < self.segment_end(i) {
+                    outermost.start
+                } else {
+                    outermost.end
+                }
+            }
+        }
+    }
+
+    /// Translates the span of an item in the derived text into the corresponding range in the original text.
+    pub fn original_span<S: Span>(&self, span: &S) -> Range<Position> {
+        let start = self.original_position(span.start_position());
+        if span.is_empty() {
+            return start..start;
+        }
+
+        let last = Position::new(span.end_position().get() - 1);
+        let Some((i, segment)) = self.segment(last) else {
+            return start..span.end_position();
+        };
+        let end = match &segment.origin {
+            Origin::Original(_) => Position::new(self.original_position(last).get() + 1),
+            Origin::Expansion(chain) => {
+                if span.end_position().get() < self.segment_end(i) {
+                    chain[chain.len() - 1].start
+                } else {
+                    chain[chain.len() - 1].end
+                }
+            }
+        };
+        start..end.max(start)
+    }
+
+    /// Returns the expansion chain (innermost first) of the given position.
+    ///
+    /// An empty slice is returned if the position is not in an expanded text.
+    pub fn expansion_chain(&self, position: Position) -> &[Range<Position>] {
+        match self.segment(position).map(|(_, s)| &s.origin) {
+            Some(Origin::Expansion(chain)) => chain,
+            _ => &[],
+        }
+    }
+
+    fn segment(&self, position: Position) -> Option<(usize, &Segment)> {
+        let i = self
+            .segments
+            .partition_point(|s| s.start <= position.get())
+            .checked_sub(1)?;
+        Some((i, &self.segments[i]))
+    }
+
+    fn segment_end(&self, i: usize) -> usize {
+        self.segments
+            .get(i + 1)
+            .map_or(self.text.len(), |s| s.start)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    start: usize,
+    origin: Origin,
+}
+
+#[derive(Debug, Clone)]
+enum Origin {
+    Original(Position),
+    Expansion(Vec<Range<Position>>),
+}
@@ -0,0 +1,182 @@
+use crate::span::LineIndex;
+use crate::{escape, Parser, Position};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+impl Parser<'_> {
+    /// Renders the memo (cf. [`Parser::memo_entries()`]) as a Graphviz DOT graph.
+    ///
+    /// Each node represents a position in the text.
+    /// A successful rule is drawn as an edge from its start position to its end position,
+    /// and a failed rule is drawn as a red box attached to the position at where it was attempted.
+    pub fn memo_to_dot(&self) -> String {
+        let text = self.text();
+        let lines = LineIndex::new(text);
+        let entries = self.memo_entries();
+
+        let mut positions = BTreeSet::new();
+        for entry in &entries {
+            positions.insert(entry.start_position());
+            positions.extend(entry.end_position());
+        }
+
+        let mut s = String::new();
+        let _ = writeln!(s, "digraph memo {{");
+        let _ = writeln!(s, "  rankdir=LR;");
+        let _ = writeln!(s, "  node [fontname=\"monospace\"];");
+        let _ = writeln!(s, "  edge [fontname=\"monospace\"];");
+        for &position in &positions {
+            let (line, column) = lines.line_and_column(position);
+            let next = text[position.get()..]
+                .chars()
+                .next()
+                .map_or_else(|| "EOS".to_owned(), |c| format!("{c:?}"));
+            let _ = writeln!(
+                s,
+                "  p{} [shape=ellipse, label=\"{}:{}\\n{}\"];",
+                position.get(),
+                line,
+                column,
+                escape::dot(&next)
+            );
+        }
+        for (prev, next) in positions.iter().zip(positions.iter().skip(1)) {
+            let _ = writeln!(
+                s,
+                "  p{} -> p{} [style=invis, weight=100];",
+                prev.get(),
+                next.get()
+            );
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let name = escape::dot(&entry.rule().short_type_name());
+            let start = entry.start_position().get();
+            if let Some(end) = entry.end_position() {
+                let _ = writeln!(
+                    s,
+                    "  p{start} -> p{} [color=darkgreen, label=\"{name}\"];",
+                    end.get()
+                );
+            } else {
+                let _ = writeln!(
+                    s,
+                    "  f{i} [shape=box, color=red, fontcolor=red, label=\"{name}\"];"
+                );
+                let _ = writeln!(s, "  p{start} -> f{i} [color=red, style=dashed];");
+            }
+        }
+        let _ = writeln!(s, "}}");
+        s
+    }
+
+    /// Renders the memo (cf. [`Parser::memo_entries()`]) as a self-contained HTML page.
+    ///
+    /// The page shows the text and the list of rule attempts.
+    /// Hovering over an attempt highlights the corresponding range of the text,
+    /// and clicking a character shows only the attempts starting at that position.
+    pub fn memo_to_html(&self) -> String {
+        let text = self.text();
+        let lines = LineIndex::new(text);
+        let location = |position: Position| {
+            let (line, column) = lines.line_and_column(position);
+            format!("{line}:{column}")
+        };
+
+        let mut s = String::new();
+        s.push_str(HTML_HEADER);
+        s.push_str("<pre id=\"source\">");
+        for (i, c) in text.char_indices() {
+            let _ = write!(
+                s,
+                "<span data-pos=\"{i}\">{}</span>",
+                escape::html(c.encode_utf8(&mut [0; 4]))
+            );
+        }
+        let _ = write!(
+            s,
+            "<span data-pos=\"{}\" class=\"eos\">&#x2400;</span>",
+            text.len()
+        );
+        s.push_str("</pre>\n");
+
+        s.push_str("<table id=\"entries\">\n");
+        s.push_str("<tr><th>rule</th><th>start</th><th>end</th><th>result</th></tr>\n");
+        for entry in self.memo_entries() {
+            let start = entry.start_position();
+            let (class, end, end_location) = if let Some(end) = entry.end_position() {
+                ("ok", end, location(end))
+            } else {
+                ("failed", start, String::from("-"))
+            };
+            let _ = writeln!(
+                s,
+                "<tr class=\"{class}\" data-start=\"{}\" data-end=\"{}\"><td title=\"{}\">{}</td><td>{}</td><td>{end_location}</td><td>{class}</td></tr>",
+                start.get(),
+                end.get(),
+                escape::html(entry.rule().type_name()),
+                escape::html(&entry.rule().short_type_name()),
+                location(start),
+            );
+        }
+        s.push_str("</table>\n");
+        s.push_str(HTML_FOOTER);
+        s
+    }
+}
+
+const HTML_HEADER: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>textparse memo</title>
+<style>
+body { font-family: sans-serif; }
+#source { border: 1px solid #ccc; padding: 8px; white-space: pre-wrap; }
+#source span { cursor: pointer; }
+#source span.ok { background: #b8f0b8; }
+#source span.failed { background: #f8b0b0; }
+#source span.selected { outline: 1px solid #333; }
+#source span.eos { color: #aaa; }
+#entries { border-collapse: collapse; font-family: monospace; }
+#entries td, #entries th { border: 1px solid #ddd; padding: 2px 6px; }
+#entries tr.ok td:last-child { color: darkgreen; }
+#entries tr.failed td:last-child { color: red; }
+#entries tr:hover { background: #eef; }
+</style>
+</head>
+<body>
+"##;
+
+const HTML_FOOTER: &str = r##"<script>
+const chars = Array.from(document.querySelectorAll("#source span"));
+const rows = Array.from(document.querySelectorAll("#entries tr[data-start]"));
+function clear() {
+  chars.forEach(c => c.classList.remove("ok", "failed"));
+}
+rows.forEach(row => {
+  row.addEventListener("mouseenter", () => {
+    const start = Number(row.dataset.start);
+    const end = Number(row.dataset.end);
+    const failed = row.classList.contains("failed");
+    chars.forEach(c => {
+      const pos = Number(c.dataset.pos);
+      if (failed ? pos === start : (start <= pos && pos < end)) {
+        c.classList.add(failed ? "failed" : "ok");
+      }
+    });
+  });
+  row.addEventListener("mouseleave", clear);
+});
+chars.forEach(c => {
+  c.addEventListener("click", () => {
+    const selected = c.classList.toggle("selected");
+    chars.forEach(other => { if (other !== c) other.classList.remove("selected"); });
+    rows.forEach(row => {
+      row.style.display = (!selected || row.dataset.start === c.dataset.pos) ? "" : "none";
+    });
+  });
+});
+</script>
+</body>
+</html>
+"##;
@@ -0,0 +1,128 @@
+use crate::{Position, Rule};
+use std::io::Write;
+use std::ops::Range;
+
+/// This trait allows for observing the events that occur while parsing.
+///
+/// All methods do nothing by default.
+pub trait ParseObserver: std::fmt::Debug {
+    /// Called when [`Parser`](crate::Parser) starts parsing an item of `rule` at `position`.
+    fn on_enter(&mut self, rule: Rule, position: Position) {
+        let _ = (rule, position);
+    }
+
+    /// Called when [`Parser`](crate::Parser) has parsed an item of `rule`.
+    fn on_success(&mut self, rule: Rule, span: Range<Position>) {
+        let _ = (rule, span);
+    }
+
+    /// Called when [`Parser`](crate::Parser) has failed to parse an item of `rule` starting at `position`.
+    fn on_failure(&mut self, rule: Rule, position: Position) {
+        let _ = (rule, position);
+    }
+
+    /// Called when the result of `rule` at `position` is taken from the memo.
+    ///
+    /// `end` is the end position of the memoized item, or `None` if the memoized result is a failure.
+    fn on_memo_hit(&mut self, rule: Rule, position: Position, end: Option<Position>) {
+        let _ = (rule, position, end);
+    }
+
+    /// Called when [`Parser`](crate::Parser) goes back from `from` to `to` due to the failure of `rule`.
+    fn on_backtrack(&mut self, rule: Rule, from: Position, to: Position) {
+        let _ = (rule, from, to);
+    }
+}
+
+/// [`ParseObserver`] implementation that prints an indented trace of parse events.
+#[derive(Debug)]
+pub struct TracePrinter<W = std::io::Stderr> {
+    writer: W,
+    depth: usize,
+}
+
+impl TracePrinter {
+    /// Makes a new [`TracePrinter`] instance that prints to the standard error.
+    pub fn stderr() -> Self {
+        Self::new(std::io::stderr())
+    }
+}
+
+impl<W: Write> TracePrinter<W> {
+    /// Makes a new [`TracePrinter`] instance that prints to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer, depth: 0 }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Converts [`TracePrinter`] into the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn print(&mut self, rule: Rule, message: std::fmt::Arguments) {
+        let indent = self.depth * 2;
+        let name = rule
+            .name()
+            .map(|name| format!(" ({name})"))
+            .unwrap_or_default();
+        let _ = writeln!(
+            self.writer,
+            "{:indent$}{}{name} {message}",
+            "",
+            rule.type_name()
+        );
+    }
+}
+
+impl<W: Write + std::fmt::Debug> ParseObserver for TracePrinter<W> {
+    fn on_enter(&mut self, rule: Rule, position: Position) {
+        self.print(rule, format_args!("@ {}", position.get()));
+        self.depth += 1;
+    }
+
+    fn on_success(&mut self, rule: Rule, span: Range<Position>) {
+        self.depth = self.depth.saturating_sub(1);
+        self.print(
+            rule,
+            format_args!("=> ok {}..{}", span.start.get(), span.end.get()),
+        );
+    }
+
+    fn on_failure(&mut self, rule: Rule, position: Position) {
+        self.depth = self.depth.saturating_sub(1);
+        self.print(rule, format_args!("=> failed @ {}", position.get()));
+    }
+
+    fn on_memo_hit(&mut self, rule: Rule, position: Position, end: Option<Position>) {
+        if let Some(end) = end {
+            self.print(
+                rule,
+                format_args!(
+                    "@ {} => ok {}..{} (memo)",
+                    position.get(),
+                    position.get(),
+                    end.get()
+                ),
+            );
+        } else {
+            self.print(rule, format_args!("@ {} => failed (memo)", position.get()));
+        }
+    }
+
+    fn on_backtrack(&mut self, rule: Rule, from: Position, to: Position) {
+        let _ = rule;
+        let indent = self.depth * 2;
+        let _ = writeln!(
+            self.writer,
+            "{:indent$}<backtrack {} -> {}>",
+            "",
+            from.get(),
+            to.get()
+        );
+    }
+}
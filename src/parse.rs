@@ -1,12 +1,20 @@
-use crate::{Position, Span};
+use crate::cst::{GreenNode, SyntaxTree};
+use crate::interval_index::IntervalIndex;
+use crate::{components::Eos, MappedText, ParseObserver, Position, Span};
 use std::fmt::Write;
 use std::{
     any::{Any, TypeId},
     borrow::{Borrow, Cow},
-    cmp::Ordering,
-    collections::{BTreeMap, HashMap},
+    cell::{Cell, RefCell},
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, HashMap, HashSet},
     error::Error,
+    ops::Range,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
 };
 
 pub use textparse_derive::Parse;
@@ -22,6 +30,13 @@ pub trait Parse: 'static + Span + Clone + Sized {
     fn name() -> Option<fn() -> String> {
         None
     }
+
+    /// Literal text of the item to be parsed if the item always matches the same text.
+    ///
+    /// This is used to provide completion candidates (cf. [`completions()`](crate::completions)).
+    fn literal() -> Option<fn() -> String> {
+        None
+    }
 }
 
 impl<T: Parse> Parse for Box<T> {
@@ -32,40 +47,57 @@ impl<T: Parse> Parse for Box<T> {
     fn name() -> Option<fn() -> String> {
         T::name()
     }
+
+    fn literal() -> Option<fn() -> String> {
+        T::literal()
+    }
 }
 
 impl<T0: Parse, T1: Parse> Parse for (T0, T1) {
     fn parse(parser: &mut Parser) -> Option<Self> {
-        Some((parser.parse()?, parser.parse()?))
+        let t0 = parser.parse()?;
+        parser.skip_trivia();
+        let t1 = parser.parse()?;
+        Some((t0, t1))
     }
 }
 
 impl<T0: Parse, T1: Parse, T2: Parse> Parse for (T0, T1, T2) {
     fn parse(parser: &mut Parser) -> Option<Self> {
-        Some((parser.parse()?, parser.parse()?, parser.parse()?))
+        let t0 = parser.parse()?;
+        parser.skip_trivia();
+        let t1 = parser.parse()?;
+        parser.skip_trivia();
+        let t2 = parser.parse()?;
+        Some((t0, t1, t2))
     }
 }
 
 impl<T0: Parse, T1: Parse, T2: Parse, T3: Parse> Parse for (T0, T1, T2, T3) {
     fn parse(parser: &mut Parser) -> Option<Self> {
-        Some((
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-        ))
+        let t0 = parser.parse()?;
+        parser.skip_trivia();
+        let t1 = parser.parse()?;
+        parser.skip_trivia();
+        let t2 = parser.parse()?;
+        parser.skip_trivia();
+        let t3 = parser.parse()?;
+        Some((t0, t1, t2, t3))
     }
 }
 
 impl<T0: Parse, T1: Parse, T2: Parse, T3: Parse, T4: Parse> Parse for (T0, T1, T2, T3, T4) {
     fn parse(parser: &mut Parser) -> Option<Self> {
-        Some((
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-        ))
+        let t0 = parser.parse()?;
+        parser.skip_trivia();
+        let t1 = parser.parse()?;
+        parser.skip_trivia();
+        let t2 = parser.parse()?;
+        parser.skip_trivia();
+        let t3 = parser.parse()?;
+        parser.skip_trivia();
+        let t4 = parser.parse()?;
+        Some((t0, t1, t2, t3, t4))
     }
 }
 
@@ -73,14 +105,130 @@ impl<T0: Parse, T1: Parse, T2: Parse, T3: Parse, T4: Parse, T5: Parse> Parse
     for (T0, T1, T2, T3, T4, T5)
 {
     fn parse(parser: &mut Parser) -> Option<Self> {
-        Some((
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-            parser.parse()?,
-        ))
+        let t0 = parser.parse()?;
+        parser.skip_trivia();
+        let t1 = parser.parse()?;
+        parser.skip_trivia();
+        let t2 = parser.parse()?;
+        parser.skip_trivia();
+        let t3 = parser.parse()?;
+        parser.skip_trivia();
+        let t4 = parser.parse()?;
+        parser.skip_trivia();
+        let t5 = parser.parse()?;
+        Some((t0, t1, t2, t3, t4, t5))
+    }
+}
+
+/// Parses the whole `text` as an item of `T`.
+///
+/// This fails if `text` has a remaining part after `T` is parsed.
+pub fn parse_str<T: Parse>(text: &str) -> Result<T, ParseError> {
+    let mut parser = Parser::new(text);
+    match parser.parse::<(T, Eos)>() {
+        Some((item, _)) => Ok(item),
+        None => Err(parser.into_parse_error()),
+    }
+}
+
+/// Parses an item of `T` from the beginning of `text` and returns it with the remaining text.
+pub fn parse_prefix<T: Parse>(text: &str) -> Result<(T, &str), ParseError> {
+    let mut parser = Parser::new(text);
+    match parser.parse::<T>() {
+        Some(item) => {
+            let remaining = &text[parser.current_position().get()..];
+            Ok((item, remaining))
+        }
+        None => Err(parser.into_parse_error()),
+    }
+}
+
+/// Reads the file at `path` and parses the whole content as an item of `T`.
+///
+/// The file path of the resulting [`ParseError`] is set to `path`.
+pub fn parse_file<T: Parse, P: AsRef<Path>>(path: P) -> Result<T, ParseFileError> {
+    let text = std::fs::read_to_string(&path)?;
+    Ok(parse_str(&text).map_err(|e| e.file_path(path))?)
+}
+
+/// Information about a rule (i.e., a type that implements [`Parse`]).
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    type_id: TypeId,
+    type_name: &'static str,
+    name: Option<fn() -> String>,
+    literal: Option<fn() -> String>,
+}
+
+/// Strips the module paths from `type_name` (e.g., `a::B<c::D>` to `B<D>`).
+pub(crate) fn short_type_name(type_name: &str) -> String {
+    let mut s = String::new();
+    let mut segment_start = 0;
+    let mut chars = type_name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            s.truncate(segment_start);
+            continue;
+        }
+        s.push(c);
+        if !(c.is_alphanumeric() || c == '_') {
+            segment_start = s.len();
+        }
+    }
+    s
+}
+
+impl PartialEq for Rule {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
+
+impl Eq for Rule {}
+
+impl std::hash::Hash for Rule {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.type_id.hash(state);
+    }
+}
+
+impl Rule {
+    /// Makes a new [`Rule`] instance for `T`.
+    pub fn of<T: Parse>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            name: T::name(),
+            literal: T::literal(),
+        }
+    }
+
+    /// Returns the type ID of the rule.
+    pub fn type_id(self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the type name of the rule (cf. [`std::any::type_name()`]).
+    pub fn type_name(self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the type name of the rule without module paths.
+    ///
+    /// For example, `textparse::components::Char<'a', true>` becomes `Char<'a', true>`.
+    pub fn short_type_name(self) -> String {
+        short_type_name(self.type_name)
+    }
+
+    /// Returns the name of the rule (cf. [`Parse::name()`]).
+    pub fn name(self) -> Option<String> {
+        self.name.map(|f| f())
+    }
+
+    /// Returns the literal text of the rule (cf. [`Parse::literal()`]).
+    pub fn literal(self) -> Option<String> {
+        self.literal.map(|f| f())
     }
 }
 
@@ -89,9 +237,26 @@ impl<T0: Parse, T1: Parse, T2: Parse, T3: Parse, T4: Parse, T5: Parse> Parse
 pub struct Parser<'a> {
     text: Cow<'a, str>,
     position: Position,
+    furthest_read_position: Cell<Position>,
     level: usize,
     expected: Expected,
-    memo: HashMap<TypeId, BTreeMap<Position, Option<Box<dyn Any>>>>,
+    memo: HashMap<TypeId, MemoTable>,
+    memo_sequence: usize,
+    children: Vec<(TypeId, Position)>,
+    memo_index: RefCell<HashMap<TypeId, IntervalIndex>>,
+    observer: Option<&'a mut dyn ParseObserver>,
+    depth: usize,
+    max_depth: Option<usize>,
+    steps: usize,
+    max_steps: Option<usize>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    aborted: Option<(ParseErrorKind, Position)>,
+    syntax: Option<Vec<GreenNode>>,
+    trivia: Option<fn(&mut Parser) -> bool>,
+    // Append-only log of the consumed trivia runs, truncated on rewinds (sorted by position).
+    trivia_ranges: Vec<Range<Position>>,
+    lexeme_depth: usize,
+    skipping_trivia: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -100,17 +265,253 @@ impl<'a> Parser<'a> {
         Self {
             text: Cow::Borrowed(text),
             position: Position::default(),
+            furthest_read_position: Cell::default(),
             level: 0,
             expected: Expected::default(),
             memo: HashMap::default(),
+            memo_sequence: 0,
+            children: Vec::new(),
+            memo_index: RefCell::default(),
+            observer: None,
+            depth: 0,
+            max_depth: None,
+            steps: 0,
+            max_steps: None,
+            cancel_flag: None,
+            aborted: None,
+            syntax: None,
+            trivia: None,
+            trivia_ranges: Vec::new(),
+            lexeme_depth: 0,
+            skipping_trivia: false,
         }
     }
 
+    /// Sets the observer that is notified of the parse events.
+    pub fn observer(mut self, observer: &'a mut dyn ParseObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sets the maximum nesting depth of rule invocations.
+    ///
+    /// If the depth exceeds `max_depth`, parsing is aborted and
+    /// the resulting [`ParseError`] has the kind [`ParseErrorKind::TooDeep`].
+    /// This is useful for preventing stack overflow on deeply nested input.
+    ///
+    /// Note that the depth counts every nested [`Parser::parse()`] call that is not resolved by the memo,
+    /// so one nesting level of a grammar usually consumes several depth units.
+    ///
+    /// The default value is `None` (unlimited).
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the maximum number of rule invocations (steps).
+    ///
+    /// If the number of [`Parser::parse()`] calls that are not resolved by the memo exceeds `max_steps`,
+    /// parsing is aborted and the resulting [`ParseError`] has the kind [`ParseErrorKind::StepLimitExceeded`].
+    ///
+    /// The default value is `None` (unlimited).
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Sets the flag to cancel parsing cooperatively.
+    ///
+    /// The flag is checked on every rule invocation.
+    /// Once it becomes `true`, parsing is aborted and
+    /// the resulting [`ParseError`] has the kind [`ParseErrorKind::Cancelled`].
+    pub fn cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(flag);
+        self
+    }
+
+    /// Enables recording a lossless concrete syntax tree during parsing.
+    ///
+    /// The recorded tree can be obtained via [`Parser::syntax_tree()`].
+    pub fn record_syntax_tree(mut self) -> Self {
+        self.syntax = Some(Vec::new());
+        self
+    }
+
+    /// Returns the concrete syntax tree recorded during parsing,
+    /// or `None` if [`Parser::record_syntax_tree()`] is not enabled.
+    ///
+    /// The nodes of the tree are the named items that make up the outermost successfully parsed items.
+    /// Empty items are not included.
+    pub fn syntax_tree(&self) -> Option<SyntaxTree> {
+        self.syntax
+            .as_ref()
+            .map(|nodes| SyntaxTree::new(self.text(), nodes))
+    }
+
+    /// Sets the trivia rule (e.g., whitespaces and comments).
+    ///
+    /// Trivia is consumed by [`Parser::skip_trivia()`]
+    /// (or the [`Trivia`](crate::components::Trivia) component) and recorded in a side table,
+    /// so it can be obtained later via [`Parser::leading_trivia()`] and [`Parser::trailing_trivia()`].
+    ///
+    /// Trivia is also skipped automatically between the fields of derived structs, tuples,
+    /// the repetitions of [`While`](crate::components::While),
+    /// and the items and delimiters of [`Items`](crate::components::Items).
+    /// To parse tokens (e.g., identifiers and string literals) without skipping,
+    /// annotate the type with `#[parse(lexeme)]` (cf. [`Parser::lexeme()`]).
+    /// To disable skipping just before a field, annotate the field with `#[parse(no_skip)]`.
+    ///
+    /// Items parsed as trivia are not reported as expected items in error messages.
+    pub fn trivia<T: Parse>(mut self) -> Self {
+        self.trivia = Some(|parser| parser.parse::<T>().is_some());
+        self
+    }
+
+    /// Returns the number of rule invocations (steps) performed so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
     /// Returns the current position.
     pub fn current_position(&self) -> Position {
         self.position
     }
 
+    /// Saves the current state of the parser.
+    ///
+    /// The saved state can be restored by [`Parser::rewind()`].
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position,
+            syntax_len: self.syntax.as_ref().map_or(0, |s| s.len()),
+            children_len: self.children.len(),
+            trivia_len: self.trivia_ranges.len(),
+        }
+    }
+
+    /// Restores the state saved by [`Parser::checkpoint()`].
+    ///
+    /// Note that the memo and the expected items for error reporting are kept as-is
+    /// because they are valid regardless of the current position.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.position = checkpoint.position;
+        if let Some(syntax) = &mut self.syntax {
+            syntax.truncate(checkpoint.syntax_len);
+        }
+        self.children.truncate(checkpoint.children_len);
+        self.trivia_ranges.truncate(checkpoint.trivia_len);
+    }
+
+    /// Calls `f` and rewinds the parser to the state before the call if `f` returns `None`.
+    pub fn attempt<T, F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut Self) -> Option<T>,
+    {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+        if result.is_none() {
+            self.rewind(checkpoint);
+        }
+        result
+    }
+
+    /// Consumes the trivia at the current position as long as possible.
+    ///
+    /// This method does nothing if the trivia rule is not set by [`Parser::trivia()`]
+    /// or if it is called within [`Parser::lexeme()`].
+    pub fn skip_trivia(&mut self) {
+        let Some(trivia) = self.trivia else {
+            return;
+        };
+        if self.lexeme_depth > 0 {
+            return;
+        }
+
+        // The trivia rule itself is parsed as a lexeme.
+        self.lexeme_depth += 1;
+        self.skipping_trivia = true;
+        let start = self.position;
+        loop {
+            let position = self.position;
+            if !trivia(self) || self.position == position {
+                break;
+            }
+        }
+        self.skipping_trivia = false;
+        self.lexeme_depth -= 1;
+        if start < self.position {
+            self.trivia_ranges.push(start..self.position);
+        }
+    }
+
+    /// Calls `f` with the automatic trivia skipping (cf. [`Parser::trivia()`]) disabled.
+    ///
+    /// Note that the memo does not distinguish whether an item was parsed within a lexeme or not,
+    /// so the same rule should not be used both inside and outside lexemes at the same position.
+    pub fn lexeme<T, F>(&mut self, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut Self) -> Option<T>,
+    {
+        self.lexeme_depth += 1;
+        let result = f(self);
+        self.lexeme_depth -= 1;
+        result
+    }
+
+    /// Returns the ranges of the trivia consumed so far.
+    ///
+    /// Each range is a maximal run of consecutive trivia items.
+    pub fn trivia_ranges(&self) -> impl '_ + Iterator<Item = Range<Position>> {
+        self.trivia_ranges.iter().cloned()
+    }
+
+    /// Returns the trivia attached to the front of `item`.
+    ///
+    /// Like Roslyn, a trivia run between two items is split at the first newline:
+    /// the part up to (and including) the newline belongs to the preceding item as trailing trivia,
+    /// and the rest belongs to the following item as leading trivia.
+    /// A trivia run at the beginning of the text is entirely leading trivia.
+    ///
+    /// If there is no such trivia, an empty range at the start position of `item` is returned.
+    pub fn leading_trivia<T: Span>(&self, item: &T) -> Range<Position> {
+        let start = item.start_position();
+        let i = self.trivia_ranges.partition_point(|run| run.start < start);
+        match i.checked_sub(1).map(|i| self.trivia_ranges[i].clone()) {
+            Some(Range {
+                start: run_start,
+                end: run_end,
+            }) if run_end == start => {
+                if run_start == Position::default() {
+                    run_start..run_end
+                } else {
+                    self.split_trivia(run_start, run_end)..run_end
+                }
+            }
+            _ => start..start,
+        }
+    }
+
+    /// Returns the trivia attached to the back of `item` (cf. [`Parser::leading_trivia()`]).
+    ///
+    /// If there is no such trivia, an empty range at the end position of `item` is returned.
+    pub fn trailing_trivia<T: Span>(&self, item: &T) -> Range<Position> {
+        let end = item.end_position();
+        let run = self
+            .trivia_ranges
+            .binary_search_by_key(&end, |run| run.start)
+            .map(|i| self.trivia_ranges[i].end);
+        match run {
+            Ok(run_end) if end != Position::default() => end..self.split_trivia(end, run_end),
+            _ => end..end,
+        }
+    }
+
+    fn split_trivia(&self, start: Position, end: Position) -> Position {
+        self.text[start.get()..end.get()]
+            .find('\n')
+            .map_or(end, |i| Position::new(start.get() + i + 1))
+    }
+
     /// Returns `true` if the parser has reached EOS, otherwise `false`.
     pub fn is_eos(&self) -> bool {
         self.text.len() == self.position.get()
@@ -121,6 +522,15 @@ impl<'a> Parser<'a> {
         self.text.borrow()
     }
 
+    /// Drops the text after `end` (which must be on a character boundary) before parsing starts.
+    pub(crate) fn truncate_text(&mut self, end: Position) {
+        debug_assert!(self.memo.is_empty());
+        match &mut self.text {
+            Cow::Borrowed(text) => *text = &text[..end.get()],
+            Cow::Owned(text) => text.truncate(end.get()),
+        }
+    }
+
     /// Returns the remaining, un-parsed text.
     pub fn remaining_text(&self) -> &str {
         &self.text[self.position.get()..]
@@ -128,6 +538,9 @@ impl<'a> Parser<'a> {
 
     /// Peeks the next character.
     pub fn peek_char(&self) -> Option<char> {
+        if self.furthest_read_position.get() < self.position {
+            self.furthest_read_position.set(self.position);
+        }
         self.remaining_text().chars().next()
     }
 
@@ -142,33 +555,80 @@ impl<'a> Parser<'a> {
     }
 
     /// Parses an item.
+    ///
+    /// Once parsing is aborted (e.g., due to [`Parser::max_depth()`] or [`Parser::cancel_flag()`]),
+    /// this method always returns `None`, including the calls that were in progress at that time.
     pub fn parse<T: Parse>(&mut self) -> Option<T> {
+        if self.aborted.is_some() {
+            return None;
+        }
+
         if let Some(result) = self.get_parse_result::<T>(self.position) {
             let result = result.cloned();
+            self.replay_memoized(TypeId::of::<T>());
+            if let Some(observer) = &mut self.observer {
+                let end = result.as_ref().map(|t| t.end_position());
+                observer.on_memo_hit(Rule::of::<T>(), self.position, end);
+            }
             if let Some(t) = &result {
+                self.children.push((TypeId::of::<T>(), self.position));
                 self.position = t.end_position();
             }
             return result;
         }
 
+        if let Some(kind) = self.check_limits() {
+            self.aborted = Some((kind, self.position));
+            return None;
+        }
+        self.steps += 1;
+
         let start = self.position;
+        let syntax_start = self.syntax.as_ref().map_or(0, |s| s.len());
+        let children_start = self.children.len();
+        let trivia_start = self.trivia_ranges.len();
+        if let Some(observer) = &mut self.observer {
+            observer.on_enter(Rule::of::<T>(), start);
+        }
 
-        let has_name = if let Some(name) = T::name() {
-            self.update_expected::<T>(name);
-            true
-        } else {
-            false
-        };
+        let has_name = T::name().is_some();
+        if has_name && !self.skipping_trivia {
+            self.update_expected::<T>();
+        }
         self.set_parse_result_if_absent::<T>(start, None);
         if has_name {
             self.level += 1;
         }
-        let result = T::parse(self);
+        self.depth += 1;
+        let mut result = T::parse(self);
+        self.depth -= 1;
+        if self.aborted.is_some() {
+            // Inner failures caused by the abort must not be read as regular failures (e.g., by `Not`).
+            result = None;
+        }
         if has_name {
             self.level -= 1;
         }
 
-        self.set_parse_result(start, result.clone());
+        let syntax = self.take_syntax_nodes::<T>(syntax_start, start, result.is_some());
+        let children = self.children.split_off(children_start);
+        let trivia = self.take_trivia_ranges(trivia_start, result.is_some());
+        if result.is_some() {
+            self.children.push((TypeId::of::<T>(), start));
+        }
+        self.set_parse_result(start, result.clone(), syntax, children, trivia);
+
+        if let Some(observer) = &mut self.observer {
+            let rule = Rule::of::<T>();
+            if result.is_some() {
+                observer.on_success(rule, start..self.position);
+            } else {
+                if self.position != start {
+                    observer.on_backtrack(rule, self.position, start);
+                }
+                observer.on_failure(rule, start);
+            }
+        }
 
         if result.is_none() {
             self.position = start;
@@ -176,117 +636,520 @@ impl<'a> Parser<'a> {
         result
     }
 
+    /// Returns the position of the furthest failure.
+    ///
+    /// [`Parser::expected_items()`] are the items that were expected at this position.
+    pub fn expected_position(&self) -> Position {
+        self.expected.position
+    }
+
+    /// Returns the named items that were expected at [`Parser::expected_position()`].
+    ///
+    /// Like the error message of [`ParseError`], if nested named items were expected at the position,
+    /// only the outermost ones are included.
+    pub fn expected_items(&self) -> impl '_ + Iterator<Item = Rule> {
+        self.expected.expected_items.values().copied()
+    }
+
+    /// Returns all the named items that were attempted at [`Parser::expected_position()`],
+    /// including the nested ones.
+    pub fn attempted_items(&self) -> impl '_ + Iterator<Item = Rule> {
+        self.expected.all_items.values().copied()
+    }
+
     /// Returns parsed items of which type is `T`.
     pub fn parsed_items<T: Parse>(&self) -> impl Iterator<Item = (Position, &T)> {
         self.memo
             .get(&TypeId::of::<T>())
             .into_iter()
-            .flat_map(|map| {
-                map.iter().filter_map(|(position, result)| {
+            .flat_map(|table| {
+                table.results.iter().filter_map(|(position, result)| {
                     result
                         .as_ref()
-                        .map(|item| (*position, item.downcast_ref::<T>().expect("unreachable")))
+                        .map(|m| (*position, m.item.downcast_ref::<T>().expect("unreachable")))
                 })
             })
     }
 
+    /// Returns parsed items of which type is `T` and of which spans overlap with `range`.
+    ///
+    /// Empty items are included if their positions are within `range`.
+    ///
+    /// This method uses an interval index that is built lazily for each type,
+    /// so the query is efficient even if there are many parsed items.
+    pub fn parsed_items_in<T: Parse>(&self, range: Range<Position>) -> Vec<(Position, &T)> {
+        let Some(table) = self.memo.get(&TypeId::of::<T>()) else {
+            return Vec::new();
+        };
+        self.query_memo_index(TypeId::of::<T>(), table, &range)
+            .into_iter()
+            .map(|span| {
+                let item = table.results[&span.start]
+                    .as_ref()
+                    .expect("unreachable")
+                    .item
+                    .downcast_ref::<T>()
+                    .expect("unreachable");
+                (span.start, item)
+            })
+            .collect()
+    }
+
+    /// Returns the non-empty parsed items of which spans cover `position`.
+    ///
+    /// The items are sorted from innermost to outermost.
+    /// This is useful for finding the node under a cursor after a successful parse.
+    pub fn items_at(&self, position: Position) -> Vec<ParsedItem<'_>> {
+        let range = position..Position::new(position.get() + 1);
+        let mut items = Vec::new();
+        for (type_id, table) in &self.memo {
+            for span in self.query_memo_index(*type_id, table, &range) {
+                if span.start == span.end {
+                    continue;
+                }
+                let item = table.results[&span.start].as_ref().expect("unreachable");
+                items.push((
+                    item.sequence,
+                    ParsedItem {
+                        rule: table.rule,
+                        start_position: span.start,
+                        end_position: span.end,
+                        item: item.item.as_ref(),
+                    },
+                ));
+            }
+        }
+        items.sort_by_key(|(sequence, item)| (item.len(), Reverse(item.start_position), *sequence));
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Returns the items that make up the items returned by the top-level [`Parser::parse()`] calls.
+    ///
+    /// Unlike [`Parser::memo_entries()`], the items that were parsed successfully
+    /// but were discarded by enclosing rules (e.g., an abandoned alternative of an enum) are excluded.
+    /// The items are sorted by start position, and outer items come before inner ones at the same position.
+    pub fn result_items(&self) -> Vec<ParsedItem<'_>> {
+        let mut items = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = self.children.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(key @ (type_id, start)) = stack.pop() {
+            if !visited.insert(key) {
+                continue;
+            }
+            let table = &self.memo[&type_id];
+            let memoized = table.results[&start].as_ref().expect("unreachable");
+            items.push(ParsedItem {
+                rule: table.rule,
+                start_position: start,
+                end_position: memoized.end_position,
+                item: memoized.item.as_ref(),
+            });
+            stack.extend(memoized.children.iter().rev().copied());
+        }
+        items
+    }
+
+    /// Returns all entries of the memo (i.e., the results of every attempted rule at every position).
+    ///
+    /// The entries are sorted by start position.
+    pub fn memo_entries(&self) -> Vec<MemoEntry> {
+        let mut entries = self
+            .memo
+            .values()
+            .flat_map(|table| {
+                table.results.iter().map(|(position, result)| MemoEntry {
+                    rule: table.rule,
+                    start_position: *position,
+                    end_position: result.as_ref().map(|m| m.end_position),
+                })
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| {
+            (a.start_position, a.end_position, a.rule.type_name()).cmp(&(
+                b.start_position,
+                b.end_position,
+                b.rule.type_name(),
+            ))
+        });
+        entries
+    }
+
+    /// Returns `true` if parsing has been aborted
+    /// (e.g., due to [`Parser::max_depth()`] or [`Parser::cancel_flag()`]).
+    pub fn is_aborted(&self) -> bool {
+        self.aborted.is_some()
+    }
+
     /// Converts [`Parser`] into [`ParseError`].
     ///
     /// You should call this method only when `Parser::parse()` returned `None`.
     pub fn into_parse_error(self) -> ParseError {
-        ParseError::new(self.into_owned())
+        ParseError::new(self)
     }
 
-    fn into_owned(self) -> Parser<'static> {
-        Parser {
-            text: Cow::Owned(self.text.into_owned()),
-            position: self.position,
-            level: self.level,
-            expected: self.expected,
-            memo: self.memo,
+    fn check_limits(&self) -> Option<ParseErrorKind> {
+        if self
+            .cancel_flag
+            .as_ref()
+            .is_some_and(|flag| flag.load(atomic::Ordering::Relaxed))
+        {
+            Some(ParseErrorKind::Cancelled)
+        } else if self.max_steps.is_some_and(|max| self.steps >= max) {
+            Some(ParseErrorKind::StepLimitExceeded)
+        } else if self.max_depth.is_some_and(|max| self.depth >= max) {
+            Some(ParseErrorKind::TooDeep)
+        } else {
+            None
         }
     }
 
-    fn update_expected<T: Parse>(&mut self, name: fn() -> String) {
+    fn update_expected<T: Parse>(&mut self) {
+        if self.expected.position <= self.position {
+            if self.expected.position < self.position {
+                self.expected.all_items.clear();
+            }
+            self.expected
+                .all_items
+                .insert(TypeId::of::<T>(), Rule::of::<T>());
+        }
         match (
             self.expected.position.cmp(&self.position),
             self.expected.level.cmp(&self.level),
         ) {
             (Ordering::Equal, Ordering::Equal) => {
-                self.expected.add_item::<T>(name);
+                self.expected.add_item::<T>();
             }
             (Ordering::Less, _) | (Ordering::Equal, Ordering::Greater) => {
-                self.expected = Expected::new::<T>(self.position, self.level, name);
+                let all_items = std::mem::take(&mut self.expected.all_items);
+                self.expected = Expected::new::<T>(self.position, self.level);
+                self.expected.all_items = all_items;
             }
             _ => {}
         }
     }
 
-    fn set_parse_result<T: Parse>(&mut self, position: Position, result: Option<T>) {
+    fn query_memo_index(
+        &self,
+        type_id: TypeId,
+        table: &MemoTable,
+        range: &Range<Position>,
+    ) -> Vec<Range<Position>> {
+        self.memo_index
+            .borrow_mut()
+            .entry(type_id)
+            .or_insert_with(|| {
+                IntervalIndex::new(
+                    table
+                        .results
+                        .iter()
+                        .filter_map(|(start, result)| {
+                            result.as_ref().map(|m| *start..m.end_position)
+                        })
+                        .collect(),
+                )
+            })
+            .query(range)
+    }
+
+    fn take_syntax_nodes<T: Parse>(
+        &mut self,
+        syntax_start: usize,
+        start: Position,
+        success: bool,
+    ) -> Vec<GreenNode> {
+        let Some(syntax) = &mut self.syntax else {
+            return Vec::new();
+        };
+        if !success {
+            syntax.truncate(syntax_start);
+            return Vec::new();
+        }
+        if T::name().is_some() && start < self.position {
+            let children = syntax.drain(syntax_start..).collect();
+            let node = GreenNode::new(Rule::of::<T>(), start..self.position, children);
+            syntax.push(node);
+        }
+        syntax[syntax_start..].to_vec()
+    }
+
+    // Restores the syntax nodes and trivia of a memoized item at the current position.
+    fn replay_memoized(&mut self, type_id: TypeId) {
+        let Some(memoized) = self.memo[&type_id].results[&self.position].as_ref() else {
+            return;
+        };
+        if let Some(syntax) = &mut self.syntax {
+            syntax.extend(memoized.syntax.iter().cloned());
+        }
+        self.trivia_ranges.extend(memoized.trivia.iter().cloned());
+    }
+
+    fn take_trivia_ranges(&mut self, trivia_start: usize, success: bool) -> Vec<Range<Position>> {
+        if !success {
+            self.trivia_ranges.truncate(trivia_start);
+            return Vec::new();
+        }
+        self.trivia_ranges[trivia_start..].to_vec()
+    }
+
+    fn set_parse_result<T: Parse>(
+        &mut self,
+        position: Position,
+        result: Option<T>,
+        syntax: Vec<GreenNode>,
+        children: Vec<(TypeId, Position)>,
+        trivia: Vec<Range<Position>>,
+    ) {
+        let memo_index = self.memo_index.get_mut();
+        if !memo_index.is_empty() {
+            memo_index.remove(&TypeId::of::<T>());
+        }
+        let sequence = self.memo_sequence;
+        self.memo_sequence += 1;
         self.memo
             .entry(TypeId::of::<T>())
-            .or_default()
-            .insert(position, result.map(|t| Box::new(t) as Box<dyn Any>));
+            .or_insert_with(MemoTable::new::<T>)
+            .results
+            .insert(
+                position,
+                result.map(|item| {
+                    Memoized::new(item, sequence)
+                        .syntax(syntax)
+                        .children(children)
+                        .trivia(trivia)
+                }),
+            );
     }
 
     fn set_parse_result_if_absent<T: Parse>(&mut self, position: Position, result: Option<T>) {
         self.memo
             .entry(TypeId::of::<T>())
-            .or_default()
+            .or_insert_with(MemoTable::new::<T>)
+            .results
             .entry(position)
-            .or_insert_with(|| result.map(|t| Box::new(t) as Box<dyn Any>));
+            .or_insert_with(|| result.map(|item| Memoized::new(item, usize::MAX)));
     }
 
     fn get_parse_result<T: Parse>(&self, position: Position) -> Option<Option<&T>> {
         self.memo
             .get(&TypeId::of::<T>())
-            .and_then(|map| map.get(&position))
+            .and_then(|table| table.results.get(&position))
             .map(|result| {
                 result
                     .as_ref()
-                    .map(|item| item.downcast_ref::<T>().expect("unreachable"))
+                    .map(|m| m.item.downcast_ref::<T>().expect("unreachable"))
             })
     }
 }
 
+/// A parsed item in the memo of [`Parser`] (cf. [`Parser::items_at()`]).
+#[derive(Debug, Clone, Copy)]
+pub struct ParsedItem<'a> {
+    rule: Rule,
+    start_position: Position,
+    end_position: Position,
+    item: &'a dyn Any,
+}
+
+impl<'a> ParsedItem<'a> {
+    /// Returns the rule of this item.
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns a reference to the item if its type is `T`, otherwise `None`.
+    pub fn downcast_ref<T: Parse>(&self) -> Option<&'a T> {
+        self.item.downcast_ref()
+    }
+}
+
+impl Span for ParsedItem<'_> {
+    fn start_position(&self) -> Position {
+        self.start_position
+    }
+
+    fn end_position(&self) -> Position {
+        self.end_position
+    }
+}
+
+/// Saved state of [`Parser`] (cf. [`Parser::checkpoint()`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    position: Position,
+    syntax_len: usize,
+    children_len: usize,
+    trivia_len: usize,
+}
+
+impl Checkpoint {
+    /// Returns the position of this checkpoint.
+    pub fn position(self) -> Position {
+        self.position
+    }
+}
+
+/// An entry of the memo of [`Parser`].
+#[derive(Debug, Clone, Copy)]
+pub struct MemoEntry {
+    rule: Rule,
+    start_position: Position,
+    end_position: Option<Position>,
+}
+
+impl MemoEntry {
+    /// Returns the rule of this entry.
+    pub fn rule(self) -> Rule {
+        self.rule
+    }
+
+    /// Returns the position at where the rule was attempted.
+    pub fn start_position(self) -> Position {
+        self.start_position
+    }
+
+    /// Returns the end position of the parsed item, or `None` if the rule failed.
+    pub fn end_position(self) -> Option<Position> {
+        self.end_position
+    }
+
+    /// Returns `true` if the rule succeeded, otherwise `false`.
+    pub fn is_success(self) -> bool {
+        self.end_position.is_some()
+    }
+}
+
+#[derive(Debug)]
+struct MemoTable {
+    rule: Rule,
+    results: BTreeMap<Position, Option<Memoized>>,
+}
+
+impl MemoTable {
+    fn new<T: Parse>() -> Self {
+        Self {
+            rule: Rule::of::<T>(),
+            results: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Memoized {
+    end_position: Position,
+    item: Box<dyn Any>,
+    syntax: Vec<GreenNode>,
+
+    // The memo keys of the items that make up this item.
+    children: Vec<(TypeId, Position)>,
+
+    // The trivia runs consumed while parsing this item (replayed on memo hits).
+    trivia: Vec<Range<Position>>,
+
+    // The order in which the parse of this item completed.
+    // An item completes before any items enclosing it.
+    sequence: usize,
+}
+
+impl Memoized {
+    fn new<T: Parse>(item: T, sequence: usize) -> Self {
+        Self {
+            end_position: item.end_position(),
+            item: Box::new(item),
+            syntax: Vec::new(),
+            children: Vec::new(),
+            trivia: Vec::new(),
+            sequence,
+        }
+    }
+
+    fn syntax(mut self, syntax: Vec<GreenNode>) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    fn children(mut self, children: Vec<(TypeId, Position)>) -> Self {
+        self.children = children;
+        self
+    }
+
+    fn trivia(mut self, trivia: Vec<Range<Position>>) -> Self {
+        self.trivia = trivia;
+        self
+    }
+}
+
 #[derive(Debug, Default)]
 struct Expected {
     position: Position,
     level: usize,
-    expected_items: HashMap<TypeId, fn() -> String>,
+    expected_items: HashMap<TypeId, Rule>,
+    all_items: HashMap<TypeId, Rule>,
 }
 
 impl Expected {
-    fn new<T: Parse>(position: Position, level: usize, name: fn() -> String) -> Self {
+    fn new<T: Parse>(position: Position, level: usize) -> Self {
         let mut this = Self {
             position,
             level,
             expected_items: Default::default(),
+            all_items: Default::default(),
         };
-        this.add_item::<T>(name);
+        this.add_item::<T>();
         this
     }
 
-    fn add_item<T: Parse>(&mut self, name: fn() -> String) {
-        self.expected_items.insert(TypeId::of::<T>(), name);
+    fn add_item<T: Parse>(&mut self) {
+        self.expected_items
+            .insert(TypeId::of::<T>(), Rule::of::<T>());
     }
 
     fn items(&self) -> impl '_ + Iterator<Item = String> {
-        self.expected_items.values().map(|f| f())
+        self.expected_items.values().filter_map(|rule| rule.name())
     }
 }
 
+/// Kind of [`ParseError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The text does not match the grammar.
+    Syntax,
+
+    /// The text reached EOS before matching the grammar.
+    ///
+    /// Unlike [`ParseErrorKind::Syntax`], appending more text could make the text valid
+    /// (e.g., a REPL can show a continuation prompt).
+    Incomplete,
+
+    /// The nesting depth exceeded the limit set by [`Parser::max_depth()`].
+    TooDeep,
+
+    /// The number of steps exceeded the limit set by [`Parser::max_steps()`].
+    StepLimitExceeded,
+
+    /// Parsing was cancelled via the flag set by [`Parser::cancel_flag()`].
+    Cancelled,
+}
+
 /// Parse error.
 pub struct ParseError {
-    parser: Parser<'static>,
+    text: String,
+    expected: Box<Expected>,
+    furthest_read_position: Position,
+    aborted: Option<(ParseErrorKind, Position)>,
     file_path: PathBuf,
+    original: Option<(String, Position)>,
 }
 
 impl ParseError {
-    fn new(parser: Parser<'static>) -> Self {
+    fn new(parser: Parser) -> Self {
         Self {
-            parser,
+            text: parser.text.into_owned(),
+            expected: Box::new(parser.expected),
+            furthest_read_position: parser.furthest_read_position.get(),
+            aborted: parser.aborted,
             file_path: PathBuf::from("<UNKNOWN>"),
+            original: None,
         }
     }
 
@@ -298,9 +1161,66 @@ impl ParseError {
         self
     }
 
+    /// Makes this error report the location in the original text of `text`.
+    ///
+    /// This is useful when the parse target text is derived from [`MappedText`].
+    pub fn map_to_original(mut self, text: &MappedText) -> Self {
+        let position = text.original_position(self.position());
+        self.original = Some((text.original_text().to_owned(), position));
+        self
+    }
+
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> ParseErrorKind {
+        if let Some((kind, _)) = self.aborted {
+            kind
+        } else if self.furthest_read_position.get() == self.text.len() {
+            ParseErrorKind::Incomplete
+        } else {
+            ParseErrorKind::Syntax
+        }
+    }
+
+    /// Returns `true` if this error is due to incomplete input, otherwise `false`.
+    ///
+    /// This is a shorthand for `self.kind() == ParseErrorKind::Incomplete`.
+    pub fn is_incomplete(&self) -> bool {
+        self.kind() == ParseErrorKind::Incomplete
+    }
+
+    /// Returns the position at where this error occurred.
+    pub fn position(&self) -> Position {
+        self.aborted
+            .map_or(self.expected.position, |(_, position)| position)
+    }
+
+    fn location(&self) -> (&str, Position) {
+        if let Some((text, position)) = &self.original {
+            (text, *position)
+        } else {
+            (&self.text, self.position())
+        }
+    }
+
     fn error_reason(&self) -> Result<String, std::fmt::Error> {
         let mut s = String::new();
-        let mut expected_items = self.parser.expected.items().collect::<Vec<_>>();
+        match self.kind() {
+            ParseErrorKind::Syntax | ParseErrorKind::Incomplete => {}
+            ParseErrorKind::TooDeep => {
+                write!(s, "nesting too deep")?;
+                return Ok(s);
+            }
+            ParseErrorKind::StepLimitExceeded => {
+                write!(s, "step limit exceeded")?;
+                return Ok(s);
+            }
+            ParseErrorKind::Cancelled => {
+                write!(s, "parsing cancelled")?;
+                return Ok(s);
+            }
+        }
+
+        let mut expected_items = self.expected.items().collect::<Vec<_>>();
         expected_items.sort();
         match expected_items.len() {
             0 => {}
@@ -324,6 +1244,46 @@ impl ParseError {
 
 impl Error for ParseError {}
 
+/// Error of [`parse_file()`].
+#[derive(Debug)]
+pub enum ParseFileError {
+    /// Failed to read the file.
+    Io(std::io::Error),
+
+    /// Failed to parse the content of the file.
+    Parse(ParseError),
+}
+
+impl From<std::io::Error> for ParseFileError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ParseError> for ParseFileError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl std::fmt::Display for ParseFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read file: {e}"),
+            Self::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ParseFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
 impl std::fmt::Debug for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self}")
@@ -332,16 +1292,13 @@ impl std::fmt::Debug for ParseError {
 
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let offset = self.parser.expected.position.get();
-        let (line, column) = self
-            .parser
-            .expected
-            .position
-            .line_and_column(&self.parser.text);
+        let (text, position) = self.location();
+        let offset = position.get();
+        let (line, column) = position.line_and_column(text);
         let reason = self.error_reason()?;
         write!(f, "{reason}")?;
 
-        if offset == self.parser.text.len() {
+        if self.is_incomplete() {
             write!(f, ", reached EOS")?;
         }
         writeln!(f)?;
@@ -357,10 +1314,7 @@ impl std::fmt::Display for ParseError {
         writeln!(
             f,
             "{line} | {}",
-            self.parser.text[offset + 1 - column..]
-                .lines()
-                .next()
-                .unwrap_or("")
+            text[offset + 1 - column..].lines().next().unwrap_or("")
         )?;
         writeln!(f, "{:line_len$} | {:>column$} {reason}", ' ', '^')?;
         Ok(())
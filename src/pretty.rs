@@ -0,0 +1,330 @@
+//! Wadler-style pretty printing for building code formatters.
+//!
+//! A parsed item is converted to a [`Doc`] via the [`Format`] trait,
+//! and then the document is laid out within a given width by [`Doc::render()`].
+use crate::{Parser, Position, Span};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// A document that can be laid out within a given width.
+#[derive(Debug, Clone)]
+pub struct Doc {
+    kind: DocKind,
+}
+
+#[derive(Debug, Clone)]
+enum DocKind {
+    Nil,
+    Text(String),
+    Line { flat: &'static str },
+    HardLine,
+    BreakParent,
+    LineSuffix(Box<Doc>),
+    Concat(Vec<Doc>),
+    Nest(usize, Box<Doc>),
+    Group(Box<Doc>),
+}
+
+impl Doc {
+    /// Makes an empty document.
+    pub fn nil() -> Self {
+        Self::new(DocKind::Nil)
+    }
+
+    /// Makes a document consisting of `text`.
+    ///
+    /// If `text` contains newlines, they are written as-is (without indentation),
+    /// and the enclosing groups are broken.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::new(DocKind::Text(text.into()))
+    }
+
+    /// Makes a line break that is rendered as a space if the enclosing group fits on a line.
+    pub fn line() -> Self {
+        Self::new(DocKind::Line { flat: " " })
+    }
+
+    /// Makes a line break that is rendered as nothing if the enclosing group fits on a line.
+    pub fn softline() -> Self {
+        Self::new(DocKind::Line { flat: "" })
+    }
+
+    /// Makes a line break that is always rendered as a newline.
+    ///
+    /// The enclosing groups are broken.
+    pub fn hardline() -> Self {
+        Self::new(DocKind::HardLine)
+    }
+
+    /// Makes a document that breaks the enclosing groups without producing any text.
+    pub fn break_parent() -> Self {
+        Self::new(DocKind::BreakParent)
+    }
+
+    /// Makes a document that is deferred until the next newline (e.g., a trailing line comment).
+    pub fn line_suffix(doc: Doc) -> Self {
+        Self::new(DocKind::LineSuffix(Box::new(doc)))
+    }
+
+    /// Concatenates documents.
+    pub fn concat(docs: impl IntoIterator<Item = Doc>) -> Self {
+        Self::new(DocKind::Concat(docs.into_iter().collect()))
+    }
+
+    /// Concatenates documents with `separator` between them.
+    pub fn join(docs: impl IntoIterator<Item = Doc>, separator: Doc) -> Self {
+        let mut result = Vec::new();
+        for (i, doc) in docs.into_iter().enumerate() {
+            if i > 0 {
+                result.push(separator.clone());
+            }
+            result.push(doc);
+        }
+        Self::concat(result)
+    }
+
+    /// Appends `other` to this document.
+    pub fn append(self, other: Doc) -> Self {
+        match self.kind {
+            DocKind::Concat(mut docs) => {
+                docs.push(other);
+                Self::concat(docs)
+            }
+            _ => Self::concat([self, other]),
+        }
+    }
+
+    /// Increases the indentation of the line breaks in this document by `indent`.
+    pub fn nest(self, indent: usize) -> Self {
+        Self::new(DocKind::Nest(indent, Box::new(self)))
+    }
+
+    /// Makes a group that is rendered on a line if it fits, otherwise its line breaks are rendered as newlines.
+    pub fn group(self) -> Self {
+        Self::new(DocKind::Group(Box::new(self)))
+    }
+
+    /// Renders this document within `width` columns.
+    pub fn render(&self, width: usize) -> String {
+        let mut out = String::new();
+        let mut column = 0;
+        let mut stack = vec![(0, Mode::Break, self)];
+        let mut suffixes = Vec::new();
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match &doc.kind {
+                DocKind::Nil | DocKind::BreakParent => {}
+                DocKind::Text(s) => {
+                    out.push_str(s);
+                    column = match s.rfind('\n') {
+                        Some(i) => s[i + 1..].chars().count(),
+                        None => column + s.chars().count(),
+                    };
+                }
+                DocKind::Line { flat } if mode == Mode::Flat => {
+                    out.push_str(flat);
+                    column += flat.chars().count();
+                }
+                DocKind::Line { .. } | DocKind::HardLine => {
+                    if !suffixes.is_empty() {
+                        stack.push((indent, mode, doc));
+                        stack.extend(suffixes.drain(..).rev());
+                        continue;
+                    }
+                    newline(&mut out, indent);
+                    column = indent;
+                }
+                DocKind::LineSuffix(d) => suffixes.push((indent, mode, &**d)),
+                DocKind::Concat(docs) => {
+                    stack.extend(docs.iter().rev().map(|d| (indent, mode, d)));
+                }
+                DocKind::Nest(n, d) => stack.push((indent + n, mode, d)),
+                DocKind::Group(d) => {
+                    let mode = if mode == Mode::Flat
+                        || fits(
+                            width.saturating_sub(column),
+                            (indent, Mode::Flat, d),
+                            &stack,
+                        ) {
+                        Mode::Flat
+                    } else {
+                        Mode::Break
+                    };
+                    stack.push((indent, mode, d));
+                }
+            }
+            if stack.is_empty() && !suffixes.is_empty() {
+                stack.extend(suffixes.drain(..).rev());
+            }
+        }
+        let len = out.trim_end_matches(' ').len();
+        out.truncate(len);
+        out
+    }
+
+    fn new(kind: DocKind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+fn newline(out: &mut String, indent: usize) {
+    let len = out.trim_end_matches(' ').len();
+    out.truncate(len);
+    out.push('\n');
+    out.extend(std::iter::repeat_n(' ', indent));
+}
+
+fn fits(mut width: usize, next: (usize, Mode, &Doc), rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut rest = rest.iter().rev().copied();
+    let mut stack = vec![next];
+    loop {
+        let Some((indent, mode, doc)) = stack.pop().or_else(|| rest.next()) else {
+            return true;
+        };
+        match &doc.kind {
+            DocKind::Nil | DocKind::LineSuffix(_) => {}
+            DocKind::Text(s) => {
+                if s.contains('\n') {
+                    return mode == Mode::Break;
+                }
+                let Some(w) = width.checked_sub(s.chars().count()) else {
+                    return false;
+                };
+                width = w;
+            }
+            DocKind::Line { flat } => {
+                if mode == Mode::Break {
+                    return true;
+                }
+                let Some(w) = width.checked_sub(flat.chars().count()) else {
+                    return false;
+                };
+                width = w;
+            }
+            DocKind::HardLine | DocKind::BreakParent => return mode == Mode::Break,
+            DocKind::Concat(docs) => stack.extend(docs.iter().rev().map(|d| (indent, mode, d))),
+            DocKind::Nest(n, d) => stack.push((indent + n, mode, d)),
+            DocKind::Group(d) => stack.push((indent, mode, d)),
+        }
+    }
+}
+
+/// This trait allows for converting a parsed item into a [`Doc`].
+pub trait Format: Span {
+    /// Converts this item into a document.
+    ///
+    /// The child items should be converted via [`FormatContext::format()`]
+    /// so that the comments and blank lines attached to them are preserved.
+    fn format(&self, ctx: &FormatContext) -> Doc;
+}
+
+impl<T: Format> Format for Box<T> {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        (**self).format(ctx)
+    }
+}
+
+/// Context of [`Format::format()`].
+///
+/// This keeps track of the trivia (cf. [`Parser::trivia()`]) attached to the formatted items.
+#[derive(Debug)]
+pub struct FormatContext<'a> {
+    parser: &'a Parser<'a>,
+    emitted_trivia: RefCell<HashSet<Position>>,
+}
+
+impl<'a> FormatContext<'a> {
+    /// Makes a new [`FormatContext`] instance.
+    ///
+    /// `parser` is the parser used to parse the items to be formatted.
+    pub fn new(parser: &'a Parser<'a>) -> Self {
+        Self {
+            parser,
+            emitted_trivia: RefCell::default(),
+        }
+    }
+
+    /// Returns the source text of `item`.
+    pub fn source_text<T: Span>(&self, item: &T) -> &'a str {
+        item.text(self.parser.text())
+    }
+
+    /// Makes a document consisting of the source text of `item`.
+    pub fn text<T: Span>(&self, item: &T) -> Doc {
+        Doc::text(self.source_text(item))
+    }
+
+    /// Converts `item` into a document with its leading and trailing trivia.
+    ///
+    /// The comments in the leading trivia are placed on their own lines before `item`,
+    /// and blank lines are preserved (consecutive blank lines are collapsed into one).
+    /// A comment in the trailing trivia is placed after `item` on the same line.
+    /// Each trivia is emitted only once even if it is attached to nested items.
+    pub fn format<T: Format>(&self, item: &T) -> Doc {
+        let leading = self.leading_trivia_doc(self.parser.leading_trivia(item));
+        let doc = item.format(self);
+        let trailing = self.trailing_trivia_doc(self.parser.trailing_trivia(item));
+        Doc::concat([leading, doc, trailing])
+    }
+
+    fn mark_emitted(&self, range: &Range<Position>) -> bool {
+        !range.is_empty() && self.emitted_trivia.borrow_mut().insert(range.start)
+    }
+
+    fn leading_trivia_doc(&self, range: Range<Position>) -> Doc {
+        if !self.mark_emitted(&range) {
+            return Doc::nil();
+        }
+
+        let mut docs = Vec::new();
+        let mut segments = self.source_text(&range).split('\n').collect::<Vec<_>>();
+        let last = segments.pop().unwrap_or("").trim();
+        let mut blank = false;
+        for segment in segments {
+            let segment = segment.trim();
+            if segment.is_empty() {
+                blank = range.start != Position::default() || !docs.is_empty();
+            } else {
+                if blank {
+                    docs.push(Doc::hardline());
+                    blank = false;
+                }
+                docs.push(Doc::text(segment));
+                docs.push(Doc::hardline());
+            }
+        }
+        if blank {
+            docs.push(Doc::hardline());
+        }
+        if !last.is_empty() {
+            docs.push(Doc::text(last));
+            docs.push(Doc::text(" "));
+        }
+        Doc::concat(docs)
+    }
+
+    fn trailing_trivia_doc(&self, range: Range<Position>) -> Doc {
+        if !self.mark_emitted(&range) {
+            return Doc::nil();
+        }
+
+        let text = self.source_text(&range);
+        let comment = text.trim();
+        if comment.is_empty() {
+            Doc::nil()
+        } else if text.ends_with('\n') {
+            Doc::concat([
+                Doc::line_suffix(Doc::text(format!(" {comment}"))),
+                Doc::break_parent(),
+            ])
+        } else {
+            Doc::text(format!(" {comment}"))
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{ParseObserver, Position, Rule};
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// [`ParseObserver`] implementation that collects per-rule statistics.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    stats: HashMap<TypeId, RuleStats>,
+    stack: Vec<Frame>,
+}
+
+impl Profiler {
+    /// Makes a new [`Profiler`] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the collected statistics as a report sorted by cost (self time, descending).
+    pub fn report(&self) -> ProfileReport {
+        let mut stats = self.stats.values().cloned().collect::<Vec<_>>();
+        stats.sort_by(|a, b| {
+            b.self_time
+                .cmp(&a.self_time)
+                .then_with(|| b.invocations.cmp(&a.invocations))
+                .then_with(|| a.rule.type_name().cmp(b.rule.type_name()))
+        });
+        ProfileReport { stats }
+    }
+
+    fn stats_mut(&mut self, rule: Rule) -> &mut RuleStats {
+        self.stats
+            .entry(rule.type_id())
+            .or_insert_with(|| RuleStats::new(rule))
+    }
+
+    fn exit(&mut self, rule: Rule) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        let elapsed = frame.start.elapsed();
+        if let Some(parent) = self.stack.last_mut() {
+            parent.children_time += elapsed;
+        }
+        let stats = self.stats_mut(rule);
+        stats.total_time += elapsed;
+        stats.self_time += elapsed.saturating_sub(frame.children_time);
+    }
+}
+
+impl ParseObserver for Profiler {
+    fn on_enter(&mut self, rule: Rule, _position: Position) {
+        let stats = self.stats_mut(rule);
+        stats.invocations += 1;
+        stats.memo_misses += 1;
+        self.stack.push(Frame {
+            start: Instant::now(),
+            children_time: Duration::ZERO,
+        });
+    }
+
+    fn on_success(&mut self, rule: Rule, _span: Range<Position>) {
+        self.exit(rule);
+    }
+
+    fn on_failure(&mut self, rule: Rule, _position: Position) {
+        self.stats_mut(rule).failures += 1;
+        self.exit(rule);
+    }
+
+    fn on_memo_hit(&mut self, rule: Rule, _position: Position, end: Option<Position>) {
+        let stats = self.stats_mut(rule);
+        stats.invocations += 1;
+        stats.memo_hits += 1;
+        if end.is_none() {
+            stats.failures += 1;
+        }
+    }
+
+    fn on_backtrack(&mut self, rule: Rule, from: Position, to: Position) {
+        self.stats_mut(rule).backtracked_bytes += from.get().saturating_sub(to.get());
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    start: Instant,
+    children_time: Duration,
+}
+
+/// Statistics of a rule collected by [`Profiler`].
+#[derive(Debug, Clone)]
+pub struct RuleStats {
+    /// Rule.
+    pub rule: Rule,
+
+    /// Number of times the rule was invoked (`memo_hits + memo_misses`).
+    pub invocations: usize,
+
+    /// Number of invocations of which results were taken from the memo.
+    pub memo_hits: usize,
+
+    /// Number of invocations that actually ran the rule.
+    pub memo_misses: usize,
+
+    /// Number of failed invocations (including memoized failures).
+    pub failures: usize,
+
+    /// Total bytes consumed by the rule before failing and being backtracked.
+    pub backtracked_bytes: usize,
+
+    /// Total time spent in the rule, including the time spent in the sub-rules.
+    pub total_time: Duration,
+
+    /// Total time spent in the rule, excluding the time spent in the sub-rules.
+    pub self_time: Duration,
+}
+
+impl RuleStats {
+    fn new(rule: Rule) -> Self {
+        Self {
+            rule,
+            invocations: 0,
+            memo_hits: 0,
+            memo_misses: 0,
+            failures: 0,
+            backtracked_bytes: 0,
+            total_time: Duration::ZERO,
+            self_time: Duration::ZERO,
+        }
+    }
+}
+
+/// Report of per-rule statistics created by [`Profiler::report()`].
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    stats: Vec<RuleStats>,
+}
+
+impl ProfileReport {
+    /// Returns the statistics sorted by cost.
+    pub fn stats(&self) -> &[RuleStats] {
+        &self.stats
+    }
+}
+
+impl std::fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>12} {:>12} {:>10} {:>10} {:>10} {:>10} {:>12}  rule",
+            "self(us)", "total(us)", "calls", "memo_hits", "misses", "failures", "backtracked"
+        )?;
+        for s in &self.stats {
+            writeln!(
+                f,
+                "{:>12} {:>12} {:>10} {:>10} {:>10} {:>10} {:>12}  {}",
+                s.self_time.as_micros(),
+                s.total_time.as_micros(),
+                s.invocations,
+                s.memo_hits,
+                s.memo_misses,
+                s.failures,
+                s.backtracked_bytes,
+                s.rule.type_name()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 pub use textparse_derive::Span;
 
 /// Position (offset) in a text.
+///
+/// If the `serde` feature is enabled, this is serialized as the offset.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Position(usize);
 
 impl Position {
@@ -31,6 +35,60 @@ impl Position {
     }
 }
 
+/// Index for converting positions into line and column numbers efficiently.
+///
+/// In addition to [`Position::line_and_column()`], this supports the zero-based, UTF-16 based
+/// line and column numbers used by the Language Server Protocol.
+#[derive(Debug)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Makes a new [`LineIndex`] instance.
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    /// Same as [`Position::line_and_column()`].
+    pub fn line_and_column(&self, position: Position) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&s| s <= position.get());
+        let line_start = self.line_starts[line - 1];
+        let column = self.text[line_start..position.get()].chars().count() + 1;
+        (line, column)
+    }
+
+    /// Returns the zero-based line number and the zero-based column number in UTF-16 code units
+    /// at where `position` is located.
+    pub fn utf16_line_and_column(&self, position: Position) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&s| s <= position.get()) - 1;
+        let line_start = self.line_starts[line];
+        let column = self.text[line_start..position.get()].encode_utf16().count();
+        (line, column)
+    }
+
+    /// Inverse of [`LineIndex::utf16_line_and_column()`].
+    ///
+    /// If `line` or `column` is out of range, the position is clamped to the end of the text or the line.
+    pub fn position_from_utf16(&self, line: usize, column: usize) -> Position {
+        let Some(&line_start) = self.line_starts.get(line) else {
+            return Position::new(self.text.len());
+        };
+        let mut utf16 = 0;
+        for (i, c) in self.text[line_start..].char_indices() {
+            if utf16 >= column || c == '\n' {
+                return Position::new(line_start + i);
+            }
+            utf16 += c.len_utf16();
+        }
+        Position::new(self.text.len())
+    }
+}
+
 /// This trait allows for representing a parsed item that has start and end positions in a text.
 pub trait Span {
     /// Returns the start position of this item.
@@ -0,0 +1,140 @@
+use std::hash::Hasher;
+
+pub use textparse_derive::{SyntaxEq, SyntaxHash};
+
+/// This trait allows for comparing parsed items by their contents, ignoring their positions.
+///
+/// For example, the items parsed from `a,b` and `a , b` are equal if the whitespaces are skipped as trivia
+/// or are parsed by [`Whitespace`](crate::components::Whitespace).
+///
+/// Components that do not keep their contents (e.g., [`While`](crate::components::While))
+/// do not implement this trait.
+/// Wrap them with [`Text`](crate::components::Text) to compare the texts matched by them.
+pub trait SyntaxEq {
+    /// Returns `true` if this item and `other` have the same contents, otherwise `false`.
+    fn syntax_eq(&self, other: &Self) -> bool;
+}
+
+/// This trait allows for hashing parsed items by their contents, ignoring their positions.
+///
+/// Implementations must be consistent with [`SyntaxEq`]:
+/// if `a.syntax_eq(&b)` holds, `a.syntax_hash()` and `b.syntax_hash()` must write the same values.
+pub trait SyntaxHash {
+    /// Feeds the contents of this item into `state`.
+    fn syntax_hash<H: Hasher>(&self, state: &mut H);
+}
+
+impl<T: SyntaxEq> SyntaxEq for Box<T> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        (**self).syntax_eq(other)
+    }
+}
+
+impl<T: SyntaxHash> SyntaxHash for Box<T> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).syntax_hash(state);
+    }
+}
+
+impl<T0: SyntaxEq, T1: SyntaxEq> SyntaxEq for (T0, T1) {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.0.syntax_eq(&other.0) && self.1.syntax_eq(&other.1)
+    }
+}
+
+impl<T0: SyntaxHash, T1: SyntaxHash> SyntaxHash for (T0, T1) {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.syntax_hash(state);
+        self.1.syntax_hash(state);
+    }
+}
+
+impl<T0: SyntaxEq, T1: SyntaxEq, T2: SyntaxEq> SyntaxEq for (T0, T1, T2) {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.0.syntax_eq(&other.0) && self.1.syntax_eq(&other.1) && self.2.syntax_eq(&other.2)
+    }
+}
+
+impl<T0: SyntaxHash, T1: SyntaxHash, T2: SyntaxHash> SyntaxHash for (T0, T1, T2) {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.syntax_hash(state);
+        self.1.syntax_hash(state);
+        self.2.syntax_hash(state);
+    }
+}
+
+impl<T0: SyntaxEq, T1: SyntaxEq, T2: SyntaxEq, T3: SyntaxEq> SyntaxEq for (T0, T1, T2, T3) {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.0.syntax_eq(&other.0)
+            && self.1.syntax_eq(&other.1)
+            && self.2.syntax_eq(&other.2)
+            && self.3.syntax_eq(&other.3)
+    }
+}
+
+impl<T0: SyntaxHash, T1: SyntaxHash, T2: SyntaxHash, T3: SyntaxHash> SyntaxHash
+    for (T0, T1, T2, T3)
+{
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.syntax_hash(state);
+        self.1.syntax_hash(state);
+        self.2.syntax_hash(state);
+        self.3.syntax_hash(state);
+    }
+}
+
+impl<T0: SyntaxEq, T1: SyntaxEq, T2: SyntaxEq, T3: SyntaxEq, T4: SyntaxEq> SyntaxEq
+    for (T0, T1, T2, T3, T4)
+{
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.0.syntax_eq(&other.0)
+            && self.1.syntax_eq(&other.1)
+            && self.2.syntax_eq(&other.2)
+            && self.3.syntax_eq(&other.3)
+            && self.4.syntax_eq(&other.4)
+    }
+}
+
+impl<T0: SyntaxHash, T1: SyntaxHash, T2: SyntaxHash, T3: SyntaxHash, T4: SyntaxHash> SyntaxHash
+    for (T0, T1, T2, T3, T4)
+{
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.syntax_hash(state);
+        self.1.syntax_hash(state);
+        self.2.syntax_hash(state);
+        self.3.syntax_hash(state);
+        self.4.syntax_hash(state);
+    }
+}
+
+impl<T0: SyntaxEq, T1: SyntaxEq, T2: SyntaxEq, T3: SyntaxEq, T4: SyntaxEq, T5: SyntaxEq> SyntaxEq
+    for (T0, T1, T2, T3, T4, T5)
+{
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.0.syntax_eq(&other.0)
+            && self.1.syntax_eq(&other.1)
+            && self.2.syntax_eq(&other.2)
+            && self.3.syntax_eq(&other.3)
+            && self.4.syntax_eq(&other.4)
+            && self.5.syntax_eq(&other.5)
+    }
+}
+
+impl<
+        T0: SyntaxHash,
+        T1: SyntaxHash,
+        T2: SyntaxHash,
+        T3: SyntaxHash,
+        T4: SyntaxHash,
+        T5: SyntaxHash,
+    > SyntaxHash for (T0, T1, T2, T3, T4, T5)
+{
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.syntax_hash(state);
+        self.1.syntax_hash(state);
+        self.2.syntax_hash(state);
+        self.3.syntax_hash(state);
+        self.4.syntax_hash(state);
+        self.5.syntax_hash(state);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{escape, ParseObserver, Position, Rule};
+use std::fmt::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// [`ParseObserver`] implementation that records parse events in the Chrome trace event format.
+///
+/// The recorded JSON can be loaded into `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/).
+#[derive(Debug)]
+pub struct TraceEventRecorder {
+    origin: Instant,
+    stack: Vec<(Position, Duration)>,
+    events: Vec<TraceEvent>,
+}
+
+impl TraceEventRecorder {
+    /// Makes a new [`TraceEventRecorder`] instance.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            stack: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Returns the recorded events as a JSON string.
+    pub fn to_json(&self) -> String {
+        let mut s = String::new();
+        s.push_str("{\"traceEvents\":[");
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                s.push(',');
+            }
+            s.push('\n');
+            event.write_json(&mut s);
+        }
+        s.push_str("\n],\"displayTimeUnit\":\"ns\"}\n");
+        s
+    }
+
+    /// Writes the recorded events to the given file as JSON.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    fn exit(&mut self, rule: Rule, end: Option<Position>) {
+        let Some((start, ts)) = self.stack.pop() else {
+            return;
+        };
+        let dur = self.origin.elapsed().saturating_sub(ts);
+        self.events.push(TraceEvent {
+            rule,
+            span: start..end.unwrap_or(start),
+            ts,
+            dur,
+            success: end.is_some(),
+            memo_hit: false,
+        });
+    }
+}
+
+impl Default for TraceEventRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParseObserver for TraceEventRecorder {
+    fn on_enter(&mut self, _rule: Rule, position: Position) {
+        self.stack.push((position, self.origin.elapsed()));
+    }
+
+    fn on_success(&mut self, rule: Rule, span: Range<Position>) {
+        self.exit(rule, Some(span.end));
+    }
+
+    fn on_failure(&mut self, rule: Rule, _position: Position) {
+        self.exit(rule, None);
+    }
+
+    fn on_memo_hit(&mut self, rule: Rule, position: Position, end: Option<Position>) {
+        self.events.push(TraceEvent {
+            rule,
+            span: position..end.unwrap_or(position),
+            ts: self.origin.elapsed(),
+            dur: Duration::ZERO,
+            success: end.is_some(),
+            memo_hit: true,
+        });
+    }
+}
+
+#[derive(Debug)]
+struct TraceEvent {
+    rule: Rule,
+    span: Range<Position>,
+    ts: Duration,
+    dur: Duration,
+    success: bool,
+    memo_hit: bool,
+}
+
+impl TraceEvent {
+    fn write_json(&self, s: &mut String) {
+        let micros = |d: Duration| d.as_nanos() as f64 / 1000.0;
+        let _ = write!(
+            s,
+            concat!(
+                "{{\"name\":\"{}\",\"cat\":\"parse\",\"ph\":\"X\",\"pid\":1,\"tid\":1,",
+                "\"ts\":{:.3},\"dur\":{:.3},",
+                "\"args\":{{\"start\":{},\"end\":{},\"success\":{},\"memo_hit\":{}}}}}"
+            ),
+            escape::json(&self.rule.short_type_name()),
+            micros(self.ts),
+            micros(self.dur),
+            self.span.start.get(),
+            self.span.end.get(),
+            self.success,
+            self.memo_hit
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::Write;
+
+pub use textparse_derive::Unparse;
+
+/// This trait allows for writing a parsed item back to text.
+///
+/// If all the parsed items carry their original text, unparsing reproduces the parsed text.
+/// Note that [`While`](crate::components::While) does not implement this trait because it has no content,
+/// so use [`Text`](crate::components::Text) to keep the text of such items.
+/// Likewise, the trivia skipped automatically (cf. [`Parser::trivia()`](crate::Parser::trivia))
+/// is not written unless it is captured by the [`Trivia`](crate::components::Trivia) component.
+pub trait Unparse {
+    /// Writes the text representation of this item to `writer`.
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result;
+
+    /// Returns the text representation of this item.
+    fn unparse_to_string(&self) -> String {
+        let mut s = String::new();
+        let _ = self.unparse(&mut s);
+        s
+    }
+}
+
+impl<T: Unparse> Unparse for Box<T> {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        (**self).unparse(writer)
+    }
+}
+
+impl<T0: Unparse, T1: Unparse> Unparse for (T0, T1) {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)?;
+        self.1.unparse(writer)
+    }
+}
+
+impl<T0: Unparse, T1: Unparse, T2: Unparse> Unparse for (T0, T1, T2) {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)?;
+        self.1.unparse(writer)?;
+        self.2.unparse(writer)
+    }
+}
+
+impl<T0: Unparse, T1: Unparse, T2: Unparse, T3: Unparse> Unparse for (T0, T1, T2, T3) {
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)?;
+        self.1.unparse(writer)?;
+        self.2.unparse(writer)?;
+        self.3.unparse(writer)
+    }
+}
+
+impl<T0: Unparse, T1: Unparse, T2: Unparse, T3: Unparse, T4: Unparse> Unparse
+    for (T0, T1, T2, T3, T4)
+{
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)?;
+        self.1.unparse(writer)?;
+        self.2.unparse(writer)?;
+        self.3.unparse(writer)?;
+        self.4.unparse(writer)
+    }
+}
+
+impl<T0: Unparse, T1: Unparse, T2: Unparse, T3: Unparse, T4: Unparse, T5: Unparse> Unparse
+    for (T0, T1, T2, T3, T4, T5)
+{
+    fn unparse(&self, writer: &mut dyn Write) -> std::fmt::Result {
+        self.0.unparse(writer)?;
+        self.1.unparse(writer)?;
+        self.2.unparse(writer)?;
+        self.3.unparse(writer)?;
+        self.4.unparse(writer)?;
+        self.5.unparse(writer)
+    }
+}
@@ -0,0 +1,155 @@
+pub use textparse_derive::{Visit, VisitMut};
+
+/// This trait allows for walking the child items of a parsed item.
+///
+/// The children of the built-in components are their direct contents
+/// (e.g., the items and delimiters of [`Items`](crate::components::Items)).
+pub trait Visit: 'static {
+    /// Calls [`Visitor::visit()`] for each child item.
+    fn walk<V: Visitor>(&self, visitor: &mut V);
+}
+
+/// Visitor of parsed items.
+///
+/// To handle specific item types, override [`Visitor::visit()`] and downcast the item as follows:
+///
+/// ```ignore
+/// fn visit<T: Visit>(&mut self, item: &T) {
+///     if let Some(s) = (item as &dyn std::any::Any).downcast_ref::<JsonString>() {
+///         // ...
+///     }
+///     item.walk(self);
+/// }
+/// ```
+pub trait Visitor: Sized {
+    /// Visits `item`.
+    ///
+    /// The default implementation visits the child items of `item`.
+    fn visit<T: Visit>(&mut self, item: &T) {
+        item.walk(self);
+    }
+}
+
+/// Mutable version of [`Visit`].
+pub trait VisitMut: 'static {
+    /// Calls [`VisitorMut::visit_mut()`] for each child item.
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V);
+}
+
+/// Mutable version of [`Visitor`].
+pub trait VisitorMut: Sized {
+    /// Visits `item`.
+    ///
+    /// The default implementation visits the child items of `item`.
+    fn visit_mut<T: VisitMut>(&mut self, item: &mut T) {
+        item.walk_mut(self);
+    }
+}
+
+impl<T: Visit> Visit for Box<T> {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&**self);
+    }
+}
+
+impl<T: VisitMut> VisitMut for Box<T> {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut **self);
+    }
+}
+
+impl<T0: Visit, T1: Visit> Visit for (T0, T1) {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+        visitor.visit(&self.1);
+    }
+}
+
+impl<T0: VisitMut, T1: VisitMut> VisitMut for (T0, T1) {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+        visitor.visit_mut(&mut self.1);
+    }
+}
+
+impl<T0: Visit, T1: Visit, T2: Visit> Visit for (T0, T1, T2) {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+        visitor.visit(&self.1);
+        visitor.visit(&self.2);
+    }
+}
+
+impl<T0: VisitMut, T1: VisitMut, T2: VisitMut> VisitMut for (T0, T1, T2) {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+        visitor.visit_mut(&mut self.1);
+        visitor.visit_mut(&mut self.2);
+    }
+}
+
+impl<T0: Visit, T1: Visit, T2: Visit, T3: Visit> Visit for (T0, T1, T2, T3) {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+        visitor.visit(&self.1);
+        visitor.visit(&self.2);
+        visitor.visit(&self.3);
+    }
+}
+
+impl<T0: VisitMut, T1: VisitMut, T2: VisitMut, T3: VisitMut> VisitMut for (T0, T1, T2, T3) {
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+        visitor.visit_mut(&mut self.1);
+        visitor.visit_mut(&mut self.2);
+        visitor.visit_mut(&mut self.3);
+    }
+}
+
+impl<T0: Visit, T1: Visit, T2: Visit, T3: Visit, T4: Visit> Visit for (T0, T1, T2, T3, T4) {
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+        visitor.visit(&self.1);
+        visitor.visit(&self.2);
+        visitor.visit(&self.3);
+        visitor.visit(&self.4);
+    }
+}
+
+impl<T0: VisitMut, T1: VisitMut, T2: VisitMut, T3: VisitMut, T4: VisitMut> VisitMut
+    for (T0, T1, T2, T3, T4)
+{
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+        visitor.visit_mut(&mut self.1);
+        visitor.visit_mut(&mut self.2);
+        visitor.visit_mut(&mut self.3);
+        visitor.visit_mut(&mut self.4);
+    }
+}
+
+impl<T0: Visit, T1: Visit, T2: Visit, T3: Visit, T4: Visit, T5: Visit> Visit
+    for (T0, T1, T2, T3, T4, T5)
+{
+    fn walk<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit(&self.0);
+        visitor.visit(&self.1);
+        visitor.visit(&self.2);
+        visitor.visit(&self.3);
+        visitor.visit(&self.4);
+        visitor.visit(&self.5);
+    }
+}
+
+impl<T0: VisitMut, T1: VisitMut, T2: VisitMut, T3: VisitMut, T4: VisitMut, T5: VisitMut> VisitMut
+    for (T0, T1, T2, T3, T4, T5)
+{
+    fn walk_mut<V: VisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_mut(&mut self.0);
+        visitor.visit_mut(&mut self.1);
+        visitor.visit_mut(&mut self.2);
+        visitor.visit_mut(&mut self.3);
+        visitor.visit_mut(&mut self.4);
+        visitor.visit_mut(&mut self.5);
+    }
+}
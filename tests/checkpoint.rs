@@ -0,0 +1,135 @@
+use textparse::{
+    components::{Char, Digit, NonEmpty, Text, While},
+    cst::SyntaxKind,
+    Parse, Parser, Position, Span,
+};
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "a number")]
+struct Number(Text<NonEmpty<While<Digit>>>);
+
+/// A list of numbers that allows a trailing comma (e.g., `[1,2,]`).
+#[derive(Clone, Span)]
+struct List {
+    start_position: Position,
+    items: Vec<Number>,
+    trailing_comma: bool,
+    end_position: Position,
+}
+
+impl Parse for List {
+    fn parse(parser: &mut Parser) -> Option<Self> {
+        let start_position = parser.current_position();
+        parser.parse::<Char<'['>>()?;
+        let mut items = Vec::new();
+        let mut trailing_comma = false;
+        if let Some(item) = parser.parse::<Number>() {
+            items.push(item);
+            // A comma is consumed only if an item follows it.
+            while let Some(item) = parser.attempt(|parser| {
+                parser.parse::<Char<','>>()?;
+                parser.parse::<Number>()
+            }) {
+                items.push(item);
+            }
+            trailing_comma = parser.parse::<Char<','>>().is_some();
+        }
+        parser.parse::<Char<']'>>()?;
+        Some(Self {
+            start_position,
+            items,
+            trailing_comma,
+            end_position: parser.current_position(),
+        })
+    }
+
+    fn name() -> Option<fn() -> String> {
+        Some(|| "a list".to_owned())
+    }
+}
+
+fn parse_list(text: &str) -> Option<(Vec<String>, bool)> {
+    let list = textparse::parse_str::<List>(text).ok()?;
+    let items = list
+        .items
+        .iter()
+        .map(|item| item.0.as_str().to_owned())
+        .collect();
+    Some((items, list.trailing_comma))
+}
+
+#[test]
+fn attempt_allows_trailing_delimiter() {
+    assert_eq!(parse_list("[]"), Some((vec![], false)));
+    assert_eq!(parse_list("[1]"), Some((vec!["1".to_owned()], false)));
+    assert_eq!(
+        parse_list("[1,23]"),
+        Some((vec!["1".to_owned(), "23".to_owned()], false))
+    );
+    assert_eq!(
+        parse_list("[1,23,]"),
+        Some((vec!["1".to_owned(), "23".to_owned()], true))
+    );
+    assert_eq!(parse_list("[,]"), None);
+    assert_eq!(parse_list("[1,,]"), None);
+}
+
+#[test]
+fn failed_attempt_rewinds_position() {
+    let mut parser = Parser::new("[1,]");
+    let checkpoint = parser.checkpoint();
+    let result = parser.attempt(|parser| {
+        parser.parse::<Char<'['>>()?;
+        parser.parse::<Number>()?;
+        parser.parse::<Char<','>>()?;
+        parser.parse::<Number>()
+    });
+    assert!(result.is_none());
+    assert_eq!(parser.current_position(), checkpoint.position());
+
+    assert!(parser.parse::<Char<'['>>().is_some());
+    let checkpoint = parser.checkpoint();
+    assert_eq!(checkpoint.position(), Position::new(1));
+    assert!(parser.parse::<(Number, Char<','>)>().is_some());
+    assert_eq!(parser.current_position(), Position::new(3));
+    parser.rewind(checkpoint);
+    assert_eq!(parser.remaining_text(), "1,]");
+}
+
+fn named_items(parser: &Parser) -> Vec<String> {
+    parser
+        .result_items()
+        .iter()
+        .filter_map(|item| item.rule().name())
+        .collect()
+}
+
+#[test]
+fn rewind_restores_recorded_items() {
+    let mut parser = Parser::new("[1,]").record_syntax_tree();
+    let checkpoint = parser.checkpoint();
+    assert!(parser.parse::<(Char<'['>, Number)>().is_some());
+    assert!(!parser.result_items().is_empty());
+    parser.rewind(checkpoint);
+    assert!(parser.result_items().is_empty());
+    let tree = parser.syntax_tree().unwrap();
+    assert!(tree.root().children().all(|node| node.is_token()));
+
+    // The comma consumed by the failed attempt in `List` is recorded only once, as the trailing comma.
+    assert!(parser.parse::<List>().is_some());
+    assert_eq!(
+        named_items(&parser),
+        ["a list", "'['", "a number", "','", "']'"]
+    );
+    let tree = parser.syntax_tree().unwrap();
+    let list = tree.root().first_child().unwrap();
+    assert_eq!(
+        list.kind(),
+        SyntaxKind::Rule(parser.result_items()[0].rule())
+    );
+    let children = list
+        .children()
+        .map(|node| node.source_text())
+        .collect::<Vec<_>>();
+    assert_eq!(children, ["[", "1", ",", "]"]);
+}
@@ -0,0 +1,47 @@
+//! The JSON grammar of `examples/check_json.rs`.
+#![allow(dead_code)]
+use textparse::{
+    components::{AnyChar, Char, Digit, Items, NonEmpty, Not, Str, Text, While, Whitespace},
+    Parse, Span, Unparse,
+};
+
+#[derive(Clone, Span, Parse, Unparse)]
+pub struct JsonValue(WithoutWhitespaces<JsonValueInner>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+#[parse(name = "a JSON value")]
+pub enum JsonValueInner {
+    Null(JsonNull),
+    String(JsonString),
+    Number(JsonNumber),
+    Array(JsonArray),
+    Object(JsonObject),
+}
+
+#[derive(Clone, Span, Parse, Unparse)]
+pub struct JsonNull(Str<'n', 'u', 'l', 'l'>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+#[parse(name = "a JSON string")]
+pub struct JsonString(Char<'"'>, Text<While<(Not<Char<'"'>>, AnyChar)>>, Char<'"'>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+#[parse(name = "a JSON number")]
+pub struct JsonNumber(Text<NonEmpty<While<Digit>>>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+#[parse(name = "a JSON array")]
+pub struct JsonArray(Char<'['>, Csv<JsonValue>, Char<']'>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+#[parse(name = "a JSON object")]
+pub struct JsonObject(Char<'{'>, Csv<JsonObjectItem>, Char<'}'>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+pub struct JsonObjectItem(WithoutWhitespaces<JsonString>, Char<':'>, JsonValue);
+
+#[derive(Clone, Span, Parse, Unparse)]
+pub struct Csv<T>(Items<T, Char<','>>);
+
+#[derive(Clone, Span, Parse, Unparse)]
+pub struct WithoutWhitespaces<T>(Text<While<Whitespace>>, T, Text<While<Whitespace>>);
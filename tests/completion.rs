@@ -0,0 +1,41 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{completions, ParseErrorKind, Parser, Position};
+
+fn names(text: &str, cursor: usize) -> (Position, Vec<String>) {
+    let completions = completions::<JsonValue>(Parser::new(text), Position::new(cursor)).unwrap();
+    let names = completions
+        .items()
+        .iter()
+        .filter_map(|item| item.name())
+        .collect();
+    (completions.position(), names)
+}
+
+#[test]
+fn completions_in_the_middle_of_an_item() {
+    let (position, names) = names("[1, nu", 6);
+    assert_eq!(position, Position::new(4));
+    assert!(names.iter().any(|name| name == "a JSON value"));
+}
+
+#[test]
+fn out_of_range_cursor_is_clamped() {
+    assert_eq!(names("[1, ", 100), names("[1, ", 4));
+}
+
+#[test]
+fn cursor_inside_a_character_is_moved_back() {
+    // `é` occupies the bytes 2..4.
+    assert_eq!(names("[\"é", 3), names("[\"é", 2));
+}
+
+#[test]
+fn aborted_parse_is_reported() {
+    let text = "[".repeat(200_000);
+    let error =
+        completions::<JsonValue>(Parser::new(&text).max_depth(100), Position::new(text.len()))
+            .unwrap_err();
+    assert_eq!(error.kind(), ParseErrorKind::TooDeep);
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{
+    components::{Char, Whitespace},
+    cst::{SyntaxKind, SyntaxNode, SyntaxTree},
+    Parse, Parser, Position, Span,
+};
+
+fn syntax_tree(text: &str) -> SyntaxTree {
+    let mut parser = Parser::new(text).record_syntax_tree();
+    assert!(parser.parse::<JsonValue>().is_some());
+    parser.syntax_tree().unwrap()
+}
+
+fn outline(node: SyntaxNode, depth: usize, out: &mut String) {
+    let kind = match node.kind() {
+        SyntaxKind::Root => "Root".to_owned(),
+        SyntaxKind::Rule(rule) => rule.short_type_name(),
+        SyntaxKind::Token => format!("{:?}", node.source_text()),
+    };
+    out.push_str(&format!("{}{kind}\n", "  ".repeat(depth)));
+    for child in node.children() {
+        outline(child, depth + 1, out);
+    }
+}
+
+fn outline_of(tree: &SyntaxTree) -> String {
+    let mut out = String::new();
+    outline(tree.root(), 0, &mut out);
+    out
+}
+
+fn assert_lossless(tree: &SyntaxTree) {
+    assert_eq!(tree.to_string(), tree.text());
+
+    // The tokens cover every byte exactly once, in order.
+    let mut offset = Position::new(0);
+    for token in tree.root().descendants().filter(|n| n.is_token()) {
+        assert_eq!(token.start_position(), offset);
+        assert!(token.start_position() < token.end_position());
+        offset = token.end_position();
+    }
+    assert_eq!(offset.get(), tree.text().len());
+
+    // The children of each node partition the span of the node.
+    for node in tree.root().descendants().filter(|n| !n.is_token()) {
+        let mut offset = node.start_position();
+        for child in node.children() {
+            assert_eq!(child.start_position(), offset);
+            offset = child.end_position();
+        }
+        assert_eq!(offset, node.end_position());
+    }
+}
+
+#[test]
+fn tree_is_lossless() {
+    for text in [
+        "null",
+        " [1, \"a b\"] ",
+        "{\"k\": [null,\n  {\"x\" :\t2}], \"\": \"\"}\n",
+        "[]",
+    ] {
+        let tree = syntax_tree(text);
+        assert_lossless(&tree);
+    }
+}
+
+#[test]
+fn tree_is_lossless_with_trivia() {
+    let text = " {\n  \"k\" : [ 1 ,2 ] ,\n  \"l\": null }\n";
+    let mut parser = Parser::new(text)
+        .trivia::<Whitespace>()
+        .record_syntax_tree();
+    assert!(parser.parse::<JsonValue>().is_some());
+    let tree = parser.syntax_tree().unwrap();
+    assert_lossless(&tree);
+    assert_eq!(outline_of(&tree), outline_of(&syntax_tree(text)));
+}
+
+#[test]
+fn nodes_are_named_rules_and_tokens() {
+    let tree = syntax_tree(" [1, \"a\"]");
+    assert_eq!(
+        outline_of(&tree),
+        r#"Root
+  " "
+  JsonValueInner
+    JsonArray
+      Char<'['>
+        "["
+      JsonValueInner
+        JsonNumber
+          "1"
+      Char<','>
+        ","
+      " "
+      JsonValueInner
+        JsonString
+          Char<'"'>
+            "\""
+          "a"
+          Char<'"'>
+            "\""
+      Char<']'>
+        "]"
+"#
+    );
+}
+
+#[test]
+fn navigation() {
+    //          0123456789
+    let text = "[1, null]";
+    let tree = syntax_tree(text);
+    let root = tree.root();
+    assert_eq!(root.kind(), SyntaxKind::Root);
+    assert!(root.parent().is_none());
+    assert!(root.next_sibling().is_none());
+    assert!(root.prev_sibling().is_none());
+
+    let array = root.first_child().unwrap().first_child().unwrap();
+    assert_eq!(array.rule().unwrap().short_type_name(), "JsonArray");
+    assert_eq!(array.source_text(), text);
+    assert_eq!(
+        array.parent().unwrap().parent().unwrap().kind(),
+        SyntaxKind::Root
+    );
+
+    let children = array
+        .children()
+        .map(|c| c.source_text())
+        .collect::<Vec<_>>();
+    assert_eq!(children, ["[", "1", ",", " ", "null", "]"]);
+
+    let comma = array.children().nth(2).unwrap();
+    assert_eq!(comma.prev_sibling().unwrap().source_text(), "1");
+    assert_eq!(comma.next_sibling().unwrap().source_text(), " ");
+    assert!(array.first_child().unwrap().prev_sibling().is_none());
+    assert!(array.last_child().unwrap().next_sibling().is_none());
+
+    // `node_at()` returns the innermost node, which is always a token.
+    let node = tree.node_at(Position::new(5));
+    assert!(node.is_token());
+    assert_eq!(node.source_text(), "null");
+    let ancestors = node
+        .ancestors()
+        .map(|n| match n.kind() {
+            SyntaxKind::Rule(rule) => rule.short_type_name(),
+            kind => format!("{kind:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ancestors,
+        [
+            "Str<'n', 'u', 'l', 'l'>",
+            "JsonValueInner",
+            "JsonArray",
+            "JsonValueInner",
+            "Root"
+        ]
+    );
+    assert_eq!(tree.node_at(Position::new(3)).source_text(), " ");
+    assert_eq!(tree.node_at(Position::new(100)).kind(), SyntaxKind::Root);
+}
+
+#[derive(Clone, Span, Parse)]
+enum Document {
+    Tagged(Tagged),
+    Plain(JsonValue),
+}
+
+#[derive(Clone, Span, Parse)]
+struct Tagged(JsonValue, Char<'!'>);
+
+#[test]
+fn memo_hits_reuse_recorded_nodes() {
+    // `Document::Plain` reuses the `JsonValue` parsed by the failed `Document::Tagged` from the memo.
+    let text = "[1, {\"a\": null}]";
+    let mut parser = Parser::new(text).record_syntax_tree();
+    let checkpoint = parser.checkpoint();
+    assert!(matches!(
+        parser.parse::<Document>(),
+        Some(Document::Plain(_))
+    ));
+    let tree = parser.syntax_tree().unwrap();
+    assert_lossless(&tree);
+    assert_eq!(outline_of(&tree), outline_of(&syntax_tree(text)));
+
+    // Parsing again after rewinding yields the same tree from the memo without duplicated nodes.
+    parser.rewind(checkpoint);
+    assert!(parser.parse::<Document>().is_some());
+    assert_eq!(
+        outline_of(&parser.syntax_tree().unwrap()),
+        outline_of(&tree)
+    );
+}
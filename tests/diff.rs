@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+use textparse::{
+    components::{Char, Digit, Items, NonEmpty, Str, While, Whitespace},
+    diff::SyntaxDiff,
+    Parse, Parser, Span,
+};
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "whitespace")]
+struct Ws(NonEmpty<While<Whitespace>>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "an array")]
+struct Array(Char<'['>, Items<Value, Char<','>>, Char<']'>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "a value")]
+enum Value {
+    Null(Null),
+    Number(Number),
+    Array(Box<Array>),
+}
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "null")]
+struct Null(Str<'n', 'u', 'l', 'l'>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "a number", lexeme)]
+struct Number(NonEmpty<While<Digit>>);
+
+fn diff(old: &str, new: &str) -> String {
+    let trees = [old, new].map(|text| {
+        let mut parser = Parser::new(text).trivia::<Ws>().record_syntax_tree();
+        assert!(parser.parse::<Array>().is_some(), "{text}");
+        parser.syntax_tree().unwrap()
+    });
+    SyntaxDiff::new(&trees[0], &trees[1]).to_string()
+}
+
+#[test]
+fn whitespace_only_changes_are_ignored() {
+    assert_eq!(diff("[1,2]", "[ 1 ,\n  2 ]"), "");
+    assert_eq!(diff("[1, [null]]", "[1,[ null ]]"), "");
+}
+
+#[test]
+fn moved_items_are_reported_without_delimiters() {
+    assert_eq!(
+        diff("[1, null, 2]", "[null, 1, 2]"),
+        "moved Value 1:2..1:3 -> 1:8..1:9: \"1\"\n"
+    );
+    assert_eq!(
+        diff("[1, 2]", "[2, 1]"),
+        "moved Value 1:2..1:3 -> 1:5..1:6: \"1\"\n"
+    );
+}
+
+#[test]
+fn changed_items_are_reported_at_the_innermost_node() {
+    assert_eq!(
+        diff("[1, [2, 3]]", "[1, [2, 4]]"),
+        "changed Number 1:9..1:10 -> 1:9..1:10: \"3\" -> \"4\"\n"
+    );
+}
+
+#[test]
+fn inserted_and_deleted_items_are_reported() {
+    assert_eq!(
+        diff("[1,2]", "[ 1 , 2,3]"),
+        "inserted Char<','> 1:8..1:9: \",\"\ninserted Value 1:9..1:10: \"3\"\n"
+    );
+    assert_eq!(
+        diff("[1, 2, 3]", "[1, 3]"),
+        "deleted Value 1:5..1:6: \"2\"\ndeleted Char<','> 1:6..1:7: \",\"\n"
+    );
+}
@@ -0,0 +1,84 @@
+use textparse::{
+    components::{AnyChar, Char, Digit, Items, NonEmpty, Not, Text, While, Whitespace},
+    Dump, Dumper, Parse, Span,
+};
+
+#[derive(Clone, Span, Parse, Dump)]
+struct Array(Char<'['>, Items<Element, Char<','>>, Char<']'>);
+
+#[derive(Clone, Span, Parse, Dump)]
+struct Element(While<Whitespace>, Value, While<Whitespace>);
+
+#[derive(Clone, Span, Parse, Dump)]
+enum Value {
+    Number(Number),
+    String(Str),
+    Array(Box<Array>),
+}
+
+#[derive(Clone, Span, Parse, Dump)]
+struct Number(Text<NonEmpty<While<Digit>>>);
+
+#[derive(Clone, Span, Parse, Dump)]
+struct Str(Char<'"'>, Text<While<(Not<Char<'"'>>, AnyChar)>>, Char<'"'>);
+
+#[test]
+fn dump_renders_indented_tree() {
+    let text = "[1,\n [\"abcdé\"]]";
+    let array = textparse::parse_str::<Array>(text).unwrap();
+    let mut dumper = Dumper::new(text).max_excerpt_chars(4);
+    array.dump(&mut dumper);
+    assert_eq!(
+        dumper.finish(),
+        r#"Array 1:1..2:12 "[1,\n"...
+  Char<'['> 1:1..1:2 "["
+  Element 1:2..1:3 "1"
+    While<Whitespace> 1:2..1:2 ""
+    Value 1:2..1:3 "1"
+      Number 1:2..1:3 "1"
+        While<Digit> 1:2..1:3 "1"
+    While<Whitespace> 1:3..1:3 ""
+  Char<','> 1:3..1:4 ","
+  Element 1:4..2:11 "\n [\""...
+    While<Whitespace> 1:4..2:2 "\n "
+    Value 2:2..2:11 "[\"ab"...
+      Array 2:2..2:11 "[\"ab"...
+        Char<'['> 2:2..2:3 "["
+        Element 2:3..2:10 "\"abc"...
+          While<Whitespace> 2:3..2:3 ""
+          Value 2:3..2:10 "\"abc"...
+            Str 2:3..2:10 "\"abc"...
+              Char<'"'> 2:3..2:4 "\""
+              While<(Not<Char<'"'>>, AnyChar)> 2:4..2:9 "abcd"...
+              Char<'"'> 2:9..2:10 "\""
+          While<Whitespace> 2:10..2:10 ""
+        Char<']'> 2:10..2:11 "]"
+    While<Whitespace> 2:11..2:11 ""
+  Char<']'> 2:11..2:12 "]"
+"#
+    );
+}
+
+#[test]
+fn excerpts_are_truncated_by_characters() {
+    // Excerpts are limited to 32 characters by default, counting `é` as one character.
+    let text = format!("\"{}é\"", "a".repeat(30));
+    let item = textparse::parse_str::<Str>(&text).unwrap();
+    assert_eq!(
+        item.dump_to_string(&text),
+        format!(
+            r#"Str 1:1..1:34 "\"{a}é"...
+  Char<'"'> 1:1..1:2 "\""
+  While<(Not<Char<'"'>>, AnyChar)> 1:2..1:33 "{a}é"
+  Char<'"'> 1:33..1:34 "\""
+"#,
+            a = "a".repeat(30)
+        )
+    );
+
+    let text = format!("\"{}é\"", "a".repeat(31));
+    let item = textparse::parse_str::<Str>(&text).unwrap();
+    assert!(item
+        .dump_to_string(&text)
+        .starts_with(&format!("Str 1:1..1:35 \"\\\"{}\"...\n", "a".repeat(31))));
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{parse_str, ParseErrorKind, Position};
+
+fn error_of(text: &str) -> (ParseErrorKind, Position) {
+    let error = parse_str::<JsonValue>(text).err().expect("parse error");
+    (error.kind(), error.position())
+}
+
+#[test]
+fn truncated_input_is_incomplete() {
+    assert_eq!(error_of(""), (ParseErrorKind::Incomplete, Position::new(0)));
+    assert_eq!(
+        error_of("nul"),
+        (ParseErrorKind::Incomplete, Position::new(0))
+    );
+    assert_eq!(
+        error_of("[1,"),
+        (ParseErrorKind::Incomplete, Position::new(3))
+    );
+    assert_eq!(
+        error_of("[\"ab"),
+        (ParseErrorKind::Incomplete, Position::new(4))
+    );
+    assert_eq!(
+        error_of("{\"a\": [nu"),
+        (ParseErrorKind::Incomplete, Position::new(7))
+    );
+}
+
+#[test]
+fn invalid_input_is_syntax_error() {
+    assert_eq!(error_of("nulx"), (ParseErrorKind::Syntax, Position::new(0)));
+    assert_eq!(error_of("[1 2"), (ParseErrorKind::Syntax, Position::new(3)));
+    assert_eq!(error_of("[1]]"), (ParseErrorKind::Syntax, Position::new(3)));
+    assert_eq!(error_of("1 x"), (ParseErrorKind::Syntax, Position::new(2)));
+}
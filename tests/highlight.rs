@@ -0,0 +1,74 @@
+use textparse::{
+    components::{Char, Digit, NonEmpty, While},
+    highlight::Highlighter,
+    Parse, Parser, Span,
+};
+
+#[derive(Clone, Span, Parse)]
+struct Num(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+struct Call(Num, Char<'('>);
+
+#[derive(Clone, Span, Parse)]
+struct Lit(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+enum E {
+    C(Call),
+    L(Lit),
+}
+
+fn parse(text: &str) -> Parser<'_> {
+    let mut parser = Parser::new(text);
+    assert!(parser.parse::<E>().is_some());
+    parser
+}
+
+#[test]
+fn abandoned_alternatives_are_not_highlighted() {
+    let parser = parse("12");
+    let highlights = Highlighter::new()
+        .class::<Num>("number")
+        .class::<Lit>("literal")
+        .highlight(&parser);
+    assert_eq!(highlights.len(), 1);
+    assert_eq!(highlights[0].class(), "literal");
+    assert_eq!(highlights[0].text("12"), "12");
+}
+
+#[test]
+fn innermost_item_is_highlighted_if_spans_are_the_same() {
+    let parser = parse("12");
+    let highlights = Highlighter::new()
+        .class::<E>("expr")
+        .class::<Lit>("literal")
+        .highlight(&parser);
+    assert_eq!(highlights.len(), 1);
+    assert_eq!(highlights[0].class(), "literal");
+}
+
+#[test]
+fn result_items_exclude_abandoned_alternatives() {
+    let parser = parse("12(");
+    let names = |parser: &Parser| {
+        parser
+            .result_items()
+            .into_iter()
+            .map(|item| {
+                item.rule()
+                    .type_name()
+                    .rsplit("::")
+                    .next()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&parser)[..3], ["E", "Call", "Num"]);
+    assert!(!names(&parser).contains(&"Lit".to_owned()));
+
+    let parser = parse("12");
+    assert_eq!(names(&parser)[..2], ["E", "Lit"]);
+    assert!(!names(&parser).contains(&"Num".to_owned()));
+}
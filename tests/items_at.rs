@@ -0,0 +1,53 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{Parser, Position};
+
+fn type_names_at(text: &str, position: usize) -> Vec<String> {
+    let mut parser = Parser::new(text);
+    assert!(parser.parse::<JsonValue>().is_some());
+    parser
+        .items_at(Position::new(position))
+        .into_iter()
+        .map(|item| {
+            item.rule()
+                .type_name()
+                .replace("textparse::components::", "")
+                .replace("items_at::common::", "")
+        })
+        .collect()
+}
+
+#[test]
+fn items_at_sorts_items_with_the_same_span_from_innermost() {
+    assert_eq!(
+        type_names_at("12", 0),
+        [
+            "Digit",
+            "While<Digit>",
+            "NonEmpty<While<Digit>>",
+            "Text<NonEmpty<While<Digit>>>",
+            "JsonNumber",
+            "JsonValueInner",
+            "WithoutWhitespaces<JsonValueInner>",
+            "JsonValue",
+        ]
+    );
+}
+
+#[test]
+fn items_at_sorts_nested_items_from_innermost() {
+    let names = type_names_at("[12, null]", 6);
+    assert_eq!(names[0], "Str<'n', 'u', 'l', 'l'>");
+    assert_eq!(names[1], "JsonNull");
+    assert_eq!(names.last().unwrap(), "JsonValue");
+    let array = names.iter().position(|name| name == "JsonArray").unwrap();
+    assert_eq!(
+        names[array + 1..],
+        [
+            "JsonValueInner",
+            "WithoutWhitespaces<JsonValueInner>",
+            "JsonValue"
+        ]
+    );
+}
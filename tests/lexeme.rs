@@ -0,0 +1,42 @@
+#![allow(dead_code)]
+use textparse::{
+    components::{Char, Digit, NonEmpty, While, Whitespace},
+    Parse, Parser, Span,
+};
+
+#[derive(Clone, Span, Parse)]
+struct Digits(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(lexeme)]
+struct Number(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+struct Sum(Number, Char<'+'>, Number);
+
+#[derive(Clone, Span, Parse)]
+struct Bang(Number, #[parse(no_skip)] Char<'!'>);
+
+fn parse<T: Parse>(text: &str) -> Option<usize> {
+    let mut parser = Parser::new(text).trivia::<Whitespace>();
+    parser.parse::<T>().map(|item| item.end_position().get())
+}
+
+#[test]
+fn lexeme_disables_trivia_skipping_inside_the_item() {
+    assert_eq!(parse::<Digits>("1 2 3"), Some(5));
+    assert_eq!(parse::<Number>("1 2 3"), Some(1));
+    assert_eq!(parse::<Number>("12 3"), Some(2));
+}
+
+#[test]
+fn trivia_is_skipped_around_lexemes() {
+    assert_eq!(parse::<Sum>("12 +\n 3"), Some(7));
+    assert_eq!(parse::<Sum>("1 2 + 3"), None);
+}
+
+#[test]
+fn no_skip_disables_trivia_skipping_before_the_field() {
+    assert_eq!(parse::<Bang>("12!"), Some(3));
+    assert_eq!(parse::<Bang>("12 !"), None);
+}
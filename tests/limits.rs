@@ -0,0 +1,77 @@
+mod common;
+
+use common::JsonValue;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use textparse::components::{Char, Not};
+use textparse::{ParseErrorKind, ParseObserver, Parser, Position, Rule};
+
+#[test]
+fn max_depth_aborts_deeply_nested_input() {
+    let text = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    let mut parser = Parser::new(&text).max_depth(1000);
+    assert!(parser.parse::<JsonValue>().is_none());
+
+    let error = parser.into_parse_error();
+    assert_eq!(error.kind(), ParseErrorKind::TooDeep);
+
+    // Each nesting level of the grammar consumes 9 depth units,
+    // so the abort happens just after the 111th bracket.
+    assert_eq!(error.position(), Position::new(1000 / 9));
+}
+
+#[test]
+fn aborted_parse_is_not_read_as_not_success() {
+    let mut parser = Parser::new("ab").max_steps(3);
+    assert!(parser.parse::<(Char<'a'>, Not<Char<'b'>>)>().is_none());
+    assert_eq!(
+        parser.into_parse_error().kind(),
+        ParseErrorKind::StepLimitExceeded
+    );
+
+    let mut parser = Parser::new("ab").max_depth(2);
+    assert!(parser.parse::<(Char<'a'>, Not<Char<'b'>>)>().is_none());
+    assert_eq!(parser.into_parse_error().kind(), ParseErrorKind::TooDeep);
+}
+
+#[test]
+fn max_steps_aborts_parsing() {
+    let text = r#"{"a": [1, 2, 3], "b": null}"#;
+    assert!(Parser::new(text).parse::<JsonValue>().is_some());
+
+    let mut parser = Parser::new(text).max_steps(20);
+    assert!(parser.parse::<JsonValue>().is_none());
+    assert_eq!(parser.steps(), 20);
+    assert_eq!(
+        parser.into_parse_error().kind(),
+        ParseErrorKind::StepLimitExceeded
+    );
+}
+
+#[test]
+fn cancel_flag_aborts_parsing() {
+    let flag = Arc::new(AtomicBool::new(true));
+    let mut parser = Parser::new("[1, 2]").cancel_flag(flag);
+    assert!(parser.parse::<JsonValue>().is_none());
+    assert_eq!(parser.into_parse_error().kind(), ParseErrorKind::Cancelled);
+}
+
+#[test]
+fn cancellation_inside_not_is_not_read_as_not_success() {
+    #[derive(Debug)]
+    struct CancelOnEnter(Rule, Arc<AtomicBool>);
+
+    impl ParseObserver for CancelOnEnter {
+        fn on_enter(&mut self, rule: Rule, _position: Position) {
+            if rule == self.0 {
+                self.1.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut observer = CancelOnEnter(Rule::of::<Not<Char<'b'>>>(), flag.clone());
+    let mut parser = Parser::new("ab").cancel_flag(flag).observer(&mut observer);
+    assert!(parser.parse::<(Char<'a'>, Not<Char<'b'>>)>().is_none());
+    assert_eq!(parser.into_parse_error().kind(), ParseErrorKind::Cancelled);
+}
@@ -0,0 +1,27 @@
+use textparse::{LineIndex, Position};
+
+#[test]
+fn utf16_line_and_column_round_trip() {
+    let text = "a\n😀b\r\nc";
+    let lines = LineIndex::new(text);
+    for (offset, expected) in [
+        (0, (0, 0)),
+        (2, (1, 0)),
+        (6, (1, 2)),
+        (9, (2, 0)),
+        (10, (2, 1)),
+    ] {
+        let position = Position::new(offset);
+        assert_eq!(lines.utf16_line_and_column(position), expected);
+        assert_eq!(lines.position_from_utf16(expected.0, expected.1), position);
+    }
+    assert_eq!(lines.line_and_column(Position::new(6)), (2, 2));
+}
+
+#[test]
+fn position_from_utf16_clamps_out_of_range() {
+    let text = "ab\ncd";
+    let lines = LineIndex::new(text);
+    assert_eq!(lines.position_from_utf16(0, 100), Position::new(2));
+    assert_eq!(lines.position_from_utf16(5, 0), Position::new(5));
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::JsonValue;
+use std::ops::Range;
+use textparse::{parse_str, MappedText, Position};
+
+fn span(start: usize, end: usize) -> Range<Position> {
+    Position::new(start)..Position::new(end)
+}
+
+// "a = FOO + b" => "a = (1+2) + b"
+fn expanded() -> MappedText {
+    let mut text = MappedText::new("a = FOO + b");
+    text.push_original(span(0, 4));
+    text.push_expansion("(1+2)", &[span(4, 7)]);
+    text.push_original(span(7, 11));
+    assert_eq!(text.text(), "a = (1+2) + b");
+    text
+}
+
+#[test]
+fn original_span_of_original_text() {
+    let text = expanded();
+    assert_eq!(text.original_span(&span(0, 1)), span(0, 1));
+    assert_eq!(text.original_span(&span(0, 4)), span(0, 4));
+    assert_eq!(text.original_span(&span(10, 13)), span(8, 11));
+}
+
+#[test]
+fn original_span_of_expanded_text() {
+    let text = expanded();
+
+    // The whole expansion is mapped to the macro invocation.
+    assert_eq!(text.original_span(&span(4, 9)), span(4, 7));
+
+    // A part of an expansion is mapped to the start of the invocation.
+    assert_eq!(text.original_span(&span(5, 6)), span(4, 4));
+
+    // Spans across expansion boundaries include the whole invocation.
+    assert_eq!(text.original_span(&span(2, 13)), span(2, 11));
+    assert_eq!(text.original_span(&span(6, 13)), span(4, 11));
+    assert_eq!(text.original_span(&span(0, 6)), span(0, 4));
+}
+
+#[test]
+fn original_span_of_empty_span() {
+    let text = expanded();
+    assert_eq!(text.original_span(&span(4, 4)), span(4, 4));
+    assert_eq!(text.original_span(&span(9, 9)), span(7, 7));
+    assert_eq!(text.original_span(&span(13, 13)), span(11, 11));
+}
+
+#[test]
+fn original_span_across_spliced_lines() {
+    // "ab\\\ncd" => "abcd"
+    let mut text = MappedText::new("ab\\\ncd");
+    text.push_original(span(0, 2));
+    text.push_original(span(4, 6));
+    assert_eq!(text.text(), "abcd");
+    assert_eq!(text.original_span(&span(1, 3)), span(1, 5));
+    assert_eq!(text.original_span(&span(2, 4)), span(4, 6));
+}
+
+#[test]
+fn parse_error_reports_original_location() {
+    // The line continuation (a backslash followed by a newline) is removed from the derived text.
+    let original = "[1,\\\n 2 x]";
+    let mut text = MappedText::new(original);
+    text.push_original(span(0, 3));
+    text.push_original(span(5, original.len()));
+    assert_eq!(text.text(), "[1, 2 x]");
+
+    let error = parse_str::<JsonValue>(text.text())
+        .err()
+        .expect("parse error")
+        .file_path("test.json")
+        .map_to_original(&text);
+    assert_eq!(error.position(), Position::new(6));
+    let message = error.to_string();
+    assert!(message.contains("--> test.json:2:4"), "{message}");
+    assert!(message.contains("2 |  2 x]"), "{message}");
+}
@@ -0,0 +1,89 @@
+use textparse::{components::Char, Parse, Parser, Position, Span};
+
+#[derive(Clone, Span, Parse)]
+struct Ab(Char<'a'>, Char<'b'>);
+
+#[derive(Clone, Span, Parse)]
+enum Item {
+    Ab(Ab),
+    A(Char<'a'>),
+}
+
+fn parser(text: &str) -> Parser<'_> {
+    let mut parser = Parser::new(text);
+    assert!(parser.parse::<Item>().is_some());
+    parser
+}
+
+#[test]
+fn memo_entries_are_sorted_by_position() {
+    let parser = parser("a\"");
+    let entries = parser
+        .memo_entries()
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.rule().short_type_name(),
+                entry.start_position().get(),
+                entry.end_position().map(Position::get),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        [
+            ("Ab".to_owned(), 0, None),
+            ("Item".to_owned(), 0, Some(1)),
+            ("Char<'a'>".to_owned(), 0, Some(1)),
+            ("Char<'b'>".to_owned(), 1, None),
+        ]
+    );
+}
+
+#[test]
+fn memo_to_dot_draws_positions_and_rules() {
+    let parser = parser("a\"");
+    assert_eq!(
+        parser.memo_to_dot(),
+        r#"digraph memo {
+  rankdir=LR;
+  node [fontname="monospace"];
+  edge [fontname="monospace"];
+  p0 [shape=ellipse, label="1:1\n'a'"];
+  p1 [shape=ellipse, label="1:2\n'\"'"];
+  p0 -> p1 [style=invis, weight=100];
+  f0 [shape=box, color=red, fontcolor=red, label="Ab"];
+  p0 -> f0 [color=red, style=dashed];
+  p0 -> p1 [color=darkgreen, label="Item"];
+  p0 -> p1 [color=darkgreen, label="Char<'a'>"];
+  f3 [shape=box, color=red, fontcolor=red, label="Char<'b'>"];
+  p1 -> f3 [color=red, style=dashed];
+}
+"#
+    );
+}
+
+#[test]
+fn memo_to_html_lists_characters_and_entries() {
+    let parser = parser("a\"");
+    let html = parser.memo_to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    assert!(html.contains(
+        r#"<pre id="source"><span data-pos="0">a</span><span data-pos="1">&quot;</span><span data-pos="2" class="eos">&#x2400;</span></pre>"#
+    ));
+
+    let rows = html
+        .lines()
+        .filter(|line| line.starts_with("<tr class="))
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), parser.memo_entries().len());
+    assert_eq!(
+        rows[0],
+        r#"<tr class="failed" data-start="0" data-end="0"><td title="memo_dump::Ab">Ab</td><td>1:1</td><td>-</td><td>failed</td></tr>"#
+    );
+    assert_eq!(
+        rows[2],
+        r#"<tr class="ok" data-start="0" data-end="1"><td title="textparse::components::Char&lt;&#39;a&#39;&gt;">Char&lt;&#39;a&#39;&gt;</td><td>1:1</td><td>1:2</td><td>ok</td></tr>"#
+    );
+}
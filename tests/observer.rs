@@ -0,0 +1,121 @@
+use std::ops::Range;
+use textparse::{
+    components::Char, Parse, ParseObserver, Parser, Position, Rule, Span, TracePrinter,
+};
+
+#[derive(Clone, Span, Parse)]
+struct Ab(Char<'a'>, Char<'b'>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "an item")]
+enum Item {
+    Ab(Ab),
+    A(Char<'a'>),
+}
+
+#[derive(Debug, Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+impl ParseObserver for Recorder {
+    fn on_enter(&mut self, rule: Rule, position: Position) {
+        self.events.push(format!(
+            "enter {} @ {}",
+            rule.short_type_name(),
+            position.get()
+        ));
+    }
+
+    fn on_success(&mut self, rule: Rule, span: Range<Position>) {
+        self.events.push(format!(
+            "success {} {}..{}",
+            rule.short_type_name(),
+            span.start.get(),
+            span.end.get()
+        ));
+    }
+
+    fn on_failure(&mut self, rule: Rule, position: Position) {
+        self.events.push(format!(
+            "failure {} @ {}",
+            rule.short_type_name(),
+            position.get()
+        ));
+    }
+
+    fn on_memo_hit(&mut self, rule: Rule, position: Position, end: Option<Position>) {
+        self.events.push(format!(
+            "memo hit {} @ {} => {:?}",
+            rule.short_type_name(),
+            position.get(),
+            end.map(|end| end.get())
+        ));
+    }
+
+    fn on_backtrack(&mut self, rule: Rule, from: Position, to: Position) {
+        self.events.push(format!(
+            "backtrack {} {} -> {}",
+            rule.short_type_name(),
+            from.get(),
+            to.get()
+        ));
+    }
+}
+
+#[test]
+fn events_are_notified_in_order() {
+    let mut recorder = Recorder::default();
+    let mut parser = Parser::new("ac").observer(&mut recorder);
+    assert!(parser.parse::<Item>().is_some());
+    assert!(parser.parse::<Item>().is_none());
+    drop(parser);
+    assert_eq!(
+        recorder.events,
+        [
+            "enter Item @ 0",
+            "enter Ab @ 0",
+            "enter Char<'a'> @ 0",
+            "success Char<'a'> 0..1",
+            "enter Char<'b'> @ 1",
+            "backtrack Char<'b'> 2 -> 1",
+            "failure Char<'b'> @ 1",
+            "backtrack Ab 1 -> 0",
+            "failure Ab @ 0",
+            "memo hit Char<'a'> @ 0 => Some(1)",
+            "success Item 0..1",
+            "enter Item @ 1",
+            "enter Ab @ 1",
+            "enter Char<'a'> @ 1",
+            "backtrack Char<'a'> 2 -> 1",
+            "failure Char<'a'> @ 1",
+            "failure Ab @ 1",
+            "memo hit Char<'a'> @ 1 => None",
+            "failure Item @ 1",
+        ]
+    );
+}
+
+#[test]
+fn trace_printer_output() {
+    let mut printer = TracePrinter::new(Vec::new());
+    let mut parser = Parser::new("ac").observer(&mut printer);
+    assert!(parser.parse::<Item>().is_some());
+    drop(parser);
+    let trace = String::from_utf8(printer.into_inner()).unwrap();
+    assert_eq!(
+        trace,
+        r#"observer::Item (an item) @ 0
+  observer::Ab @ 0
+    textparse::components::Char<'a'> ('a') @ 0
+    textparse::components::Char<'a'> ('a') => ok 0..1
+    textparse::components::Char<'b'> ('b') @ 1
+      <backtrack 2 -> 1>
+    textparse::components::Char<'b'> ('b') => failed @ 1
+    <backtrack 1 -> 0>
+  observer::Ab => failed @ 0
+  textparse::components::Char<'a'> ('a') @ 0 => ok 0..1 (memo)
+observer::Item (an item) => ok 0..1
+"#
+    );
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{parse_file, ParseErrorKind, ParseFileError};
+
+fn read_json(path: &std::path::Path) -> anyhow::Result<JsonValue> {
+    Ok(parse_file(path)?)
+}
+
+#[test]
+fn parse_file_reports_io_and_parse_errors() {
+    let dir = std::env::temp_dir().join(format!("textparse-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let valid = dir.join("valid.json");
+    std::fs::write(&valid, "[1, null]").unwrap();
+    assert!(read_json(&valid).is_ok());
+
+    let invalid = dir.join("invalid.json");
+    std::fs::write(&invalid, "[1 null]").unwrap();
+    match parse_file::<JsonValue, _>(&invalid) {
+        Err(ParseFileError::Parse(e)) => {
+            assert_eq!(e.kind(), ParseErrorKind::Syntax);
+            assert!(e.to_string().contains("invalid.json:1:4"), "{e}");
+        }
+        other => panic!("unexpected result: {:?}", other.err()),
+    }
+
+    let missing = dir.join("missing.json");
+    assert!(matches!(
+        parse_file::<JsonValue, _>(&missing),
+        Err(ParseFileError::Io(_))
+    ));
+    assert!(read_json(&missing).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
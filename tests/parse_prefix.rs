@@ -0,0 +1,24 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{parse_prefix, ParseErrorKind, Position, Span};
+
+#[test]
+fn parse_prefix_returns_remaining_text() {
+    let (value, remaining) = parse_prefix::<JsonValue>("[1, 2]  ; rest").unwrap();
+    assert_eq!(value.end_position(), Position::new(8));
+    assert_eq!(remaining, "; rest");
+
+    let (_, remaining) = parse_prefix::<JsonValue>("null").unwrap();
+    assert_eq!(remaining, "");
+
+    let (_, remaining) = parse_prefix::<JsonValue>("\"é\"→ü").unwrap();
+    assert_eq!(remaining, "→ü");
+}
+
+#[test]
+fn parse_prefix_fails_without_prefix() {
+    let error = parse_prefix::<JsonValue>("; [1]").err().unwrap();
+    assert_eq!(error.kind(), ParseErrorKind::Syntax);
+    assert_eq!(error.position(), Position::new(0));
+}
@@ -0,0 +1,182 @@
+#![allow(dead_code)]
+use textparse::{
+    components::{AnyChar, Char, Digit, Items, NonEmpty, Not, Text, While, Whitespace},
+    pretty::{Doc, Format, FormatContext},
+    Parse, Parser, Span,
+};
+
+fn list(items: &[&str]) -> Doc {
+    Doc::concat([
+        Doc::text("["),
+        Doc::concat([
+            Doc::softline(),
+            Doc::join(
+                items.iter().map(|item| Doc::text(*item)),
+                Doc::text(",").append(Doc::line()),
+            ),
+        ])
+        .nest(2),
+        Doc::softline(),
+        Doc::text("]"),
+    ])
+    .group()
+}
+
+#[test]
+fn group_is_flat_if_it_fits() {
+    let doc = list(&["1", "2", "3"]);
+    assert_eq!(doc.render(9), "[1, 2, 3]");
+    assert_eq!(doc.render(8), "[\n  1,\n  2,\n  3\n]");
+}
+
+#[test]
+fn inner_groups_are_laid_out_independently() {
+    let doc = list(&["1", "2"]);
+    let doc = Doc::concat([
+        Doc::text("f("),
+        Doc::concat([
+            Doc::softline(),
+            Doc::join([doc.clone(), doc], Doc::text(",").append(Doc::line())),
+        ])
+        .nest(4),
+        Doc::softline(),
+        Doc::text(")"),
+    ])
+    .group();
+    assert_eq!(doc.render(80), "f([1, 2], [1, 2])");
+    assert_eq!(doc.render(12), "f(\n    [1, 2],\n    [1, 2]\n)");
+    assert_eq!(
+        doc.render(6),
+        "f(\n    [\n      1,\n      2\n    ],\n    [\n      1,\n      2\n    ]\n)"
+    );
+}
+
+#[test]
+fn nest_indents_line_breaks() {
+    let doc = Doc::text("a").append(
+        Doc::concat([
+            Doc::hardline(),
+            Doc::text("b"),
+            Doc::hardline().nest(2),
+            Doc::text("c"),
+        ])
+        .nest(2),
+    );
+    assert_eq!(doc.render(80), "a\n  b\n    c");
+
+    // Trailing spaces before line breaks (e.g., of empty indented lines) are removed.
+    let doc = Doc::concat([
+        Doc::text("a"),
+        Doc::hardline(),
+        Doc::hardline(),
+        Doc::text("b"),
+    ])
+    .nest(2);
+    assert_eq!(doc.render(80), "a\n\n  b");
+}
+
+#[test]
+fn line_suffix_is_deferred_until_the_next_newline() {
+    let doc = Doc::concat([
+        Doc::text("a"),
+        Doc::line_suffix(Doc::text(" // a")),
+        Doc::text(","),
+        Doc::hardline(),
+        Doc::text("b"),
+        Doc::line_suffix(Doc::text(" // b")),
+        Doc::text(";"),
+    ]);
+    assert_eq!(doc.render(80), "a, // a\nb; // b");
+}
+
+#[test]
+fn break_parent_breaks_enclosing_groups() {
+    let doc = Doc::concat([Doc::text("a"), Doc::line(), Doc::text("b")]).group();
+    assert_eq!(doc.render(80), "a b");
+
+    let doc = Doc::concat([
+        Doc::text("a"),
+        Doc::line(),
+        Doc::break_parent(),
+        Doc::text("b"),
+    ]);
+    let doc = Doc::concat([doc.group(), Doc::line(), Doc::text("c")]).group();
+    assert_eq!(doc.render(80), "a\nb\nc");
+
+    // Texts with newlines also break the enclosing groups.
+    let doc = Doc::concat([Doc::text("a"), Doc::line(), Doc::text("b\nc")]).group();
+    assert_eq!(doc.render(80), "a\nb\nc");
+}
+
+#[derive(Clone, Span, Parse)]
+enum Blank {
+    Space(Whitespace),
+    Comment(Comment),
+}
+
+#[derive(Clone, Span, Parse)]
+struct Comment(Char<'#'>, While<(Not<Char<'\n'>>, AnyChar)>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(lexeme)]
+struct Word(Text<NonEmpty<While<Digit<36>>>>);
+
+#[derive(Clone, Span, Parse)]
+struct Assign(Word, Char<'='>, Word);
+
+#[derive(Clone, Span, Parse)]
+struct Program(Items<Assign, Char<';'>>);
+
+impl Format for Word {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        ctx.text(self)
+    }
+}
+
+impl Format for Assign {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        Doc::concat([
+            ctx.format(&self.0),
+            Doc::text(" "),
+            ctx.format(&self.1),
+            Doc::text(" "),
+            ctx.format(&self.2),
+        ])
+    }
+}
+
+impl Format for Program {
+    fn format(&self, ctx: &FormatContext) -> Doc {
+        let mut docs = Vec::new();
+        for (i, item) in self.0.items().iter().enumerate() {
+            docs.push(ctx.format(item));
+            if let Some(delimiter) = self.0.delimiters().get(i) {
+                docs.push(ctx.format(delimiter));
+                docs.push(Doc::hardline());
+            }
+        }
+        Doc::concat(docs)
+    }
+}
+
+fn format(text: &str) -> String {
+    let mut parser = Parser::new(text).trivia::<Blank>();
+    parser.skip_trivia();
+    let program = parser.parse::<Program>().unwrap();
+    parser.skip_trivia();
+    assert!(parser.is_eos());
+    FormatContext::new(&parser).format(&program).render(80)
+}
+
+#[test]
+fn comments_and_blank_lines_are_preserved() {
+    let text = "# header\n\na=1;   # one\n# about b\n\n\n\nb =  2  # two\n";
+    assert_eq!(
+        format(text),
+        "# header\n\na = 1; # one\n# about b\n\nb = 2 # two"
+    );
+
+    // Formatting is idempotent.
+    let formatted = format(text);
+    assert_eq!(format(&formatted), formatted);
+}
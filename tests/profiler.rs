@@ -0,0 +1,78 @@
+use textparse::{components::Char, Parse, Parser, Profiler, Rule, RuleStats, Span};
+
+#[derive(Clone, Span, Parse)]
+struct Ab(Char<'a'>, Char<'b'>);
+
+#[derive(Clone, Span, Parse)]
+enum Item {
+    Ab(Ab),
+    A(Char<'a'>),
+}
+
+fn counts(stats: &RuleStats) -> [usize; 5] {
+    [
+        stats.invocations,
+        stats.memo_hits,
+        stats.memo_misses,
+        stats.failures,
+        stats.backtracked_bytes,
+    ]
+}
+
+#[test]
+fn profiler_counts_invocations() {
+    let mut profiler = Profiler::new();
+    let mut parser = Parser::new("ac").observer(&mut profiler);
+    assert!(parser.parse::<Item>().is_some());
+    assert!(parser.parse::<Item>().is_none());
+    drop(parser);
+
+    let report = profiler.report();
+    let stats_of = |rule: Rule| {
+        report
+            .stats()
+            .iter()
+            .find(|s| s.rule == rule)
+            .map(counts)
+            .unwrap()
+    };
+    // [invocations, memo_hits, memo_misses, failures, backtracked_bytes]
+    assert_eq!(stats_of(Rule::of::<Item>()), [2, 0, 2, 1, 0]);
+    assert_eq!(stats_of(Rule::of::<Ab>()), [2, 0, 2, 2, 1]);
+    assert_eq!(stats_of(Rule::of::<Char<'a'>>()), [4, 2, 2, 2, 1]);
+    assert_eq!(stats_of(Rule::of::<Char<'b'>>()), [1, 0, 1, 1, 1]);
+    assert_eq!(report.stats().len(), 4);
+
+    for s in report.stats() {
+        assert_eq!(s.invocations, s.memo_hits + s.memo_misses);
+        assert!(s.self_time <= s.total_time);
+    }
+}
+
+#[test]
+fn report_is_sorted_by_self_time() {
+    let mut profiler = Profiler::new();
+    let mut parser = Parser::new("ac").observer(&mut profiler);
+    assert!(parser.parse::<Item>().is_some());
+    drop(parser);
+
+    let report = profiler.report();
+    let stats = report.stats();
+    for pair in stats.windows(2) {
+        assert!(
+            (pair[0].self_time, pair[0].invocations) >= (pair[1].self_time, pair[1].invocations),
+            "{report}"
+        );
+    }
+
+    let lines = report.to_string();
+    let lines = lines.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), stats.len() + 1);
+    assert!(lines[0].ends_with("  rule"));
+    for (line, s) in lines[1..].iter().zip(stats) {
+        assert!(
+            line.ends_with(&format!("  {}", s.rule.type_name())),
+            "{line}"
+        );
+    }
+}
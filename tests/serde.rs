@@ -0,0 +1,58 @@
+#![cfg(feature = "serde")]
+use textparse::{
+    components::{Char, Digit, Items, Whitespace},
+    Parser, Position, Span,
+};
+
+type Digits = Items<Digit, Char<','>>;
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(item: &T) -> T {
+    let json = serde_json::to_string(item).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn items_round_trip() {
+    let text = "1, 2 ,3";
+    let items = Parser::new(text)
+        .trivia::<Whitespace>()
+        .parse::<Digits>()
+        .unwrap();
+    let json = serde_json::to_value(&items).unwrap();
+    assert!(json.get("position").is_none());
+    assert_eq!(json["items"].as_array().unwrap().len(), 3);
+    assert_eq!(json["delimiters"].as_array().unwrap().len(), 2);
+
+    let restored = round_trip(&items);
+    assert_eq!(restored.start_position(), Position::new(0));
+    assert_eq!(restored.end_position(), Position::new(text.len()));
+    let digits = |items: &Digits| items.items().iter().map(|d| d.get()).collect::<Vec<_>>();
+    assert_eq!(digits(&restored), [1, 2, 3]);
+    let delimiters = restored
+        .delimiters()
+        .iter()
+        .map(|d| d.start_position().get())
+        .collect::<Vec<_>>();
+    assert_eq!(delimiters, [1, 5]);
+}
+
+#[test]
+fn empty_items_round_trip() {
+    let items = Parser::new("x").parse::<Digits>().unwrap();
+    assert_eq!(
+        serde_json::to_value(&items).unwrap(),
+        serde_json::json!({"position": 0, "items": [], "delimiters": []})
+    );
+    let restored = round_trip(&items);
+    assert!(restored.items().is_empty());
+    assert_eq!(restored.start_position(), Position::new(0));
+    assert_eq!(restored.end_position(), Position::new(0));
+}
+
+#[test]
+fn items_with_mismatched_delimiters_are_rejected() {
+    let items = Parser::new("1,2").parse::<Digits>().unwrap();
+    let mut json = serde_json::to_value(&items).unwrap();
+    json["delimiters"] = serde_json::json!([]);
+    assert!(serde_json::from_value::<Digits>(json).is_err());
+}
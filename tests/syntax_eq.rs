@@ -0,0 +1,43 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use textparse::{
+    components::{Char, Digit, Items, NonEmpty, Text, While, Whitespace},
+    Parse, Parser, Span, SyntaxEq, SyntaxHash,
+};
+
+#[derive(Clone, Span, Parse, SyntaxEq, SyntaxHash)]
+struct Array(Char<'['>, Items<Number, Char<','>>, Char<']'>);
+
+#[derive(Clone, Span, Parse, SyntaxEq, SyntaxHash)]
+struct Number(Text<NonEmpty<While<Digit>>>);
+
+fn parse(text: &str) -> Array {
+    Parser::new(text)
+        .trivia::<Whitespace>()
+        .parse()
+        .expect("parse error")
+}
+
+fn hash(array: &Array) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    array.syntax_hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn syntax_eq_ignores_positions_and_trivia() {
+    let a = parse("[1,23]");
+    let b = parse("[ 1 , 23 ]");
+    assert!(a.syntax_eq(&b));
+    assert_eq!(hash(&a), hash(&b));
+}
+
+#[test]
+fn syntax_eq_compares_texts_of_repetitions() {
+    let a = parse("[1,2]");
+    let b = parse("[1,9]");
+    let c = parse("[1,22]");
+    assert!(!a.syntax_eq(&b));
+    assert!(!a.syntax_eq(&c));
+    assert_ne!(hash(&a), hash(&b));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::JsonValue;
+use serde_json::Value;
+use textparse::{Parser, TraceEventRecorder};
+
+#[test]
+fn trace_events_of_check_json_grammar() {
+    let text = r#"{"a": [1, "x"], "b": null}"#;
+    let mut recorder = TraceEventRecorder::new();
+    let mut parser = Parser::new(text).observer(&mut recorder);
+    assert!(parser.parse::<JsonValue>().is_some());
+    drop(parser);
+
+    let json: Value = serde_json::from_str(&recorder.to_json()).expect("valid JSON");
+    let events = json["traceEvents"].as_array().expect("traceEvents array");
+    assert!(!events.is_empty());
+
+    let mut spans = Vec::new();
+    for event in events {
+        assert!(event["name"].is_string(), "{event}");
+        assert_eq!(event["ph"], "X", "{event}");
+        let ts = event["ts"].as_f64().expect("ts");
+        let dur = event["dur"].as_f64().expect("dur");
+        let args = &event["args"];
+        let start = args["start"].as_u64().expect("args.start");
+        let end = args["end"].as_u64().expect("args.end");
+        let success = args["success"].as_bool().expect("args.success");
+        assert!(args["memo_hit"].is_boolean(), "{event}");
+        assert!(start <= end && end as usize <= text.len(), "{event}");
+        spans.push((ts, ts + dur, start, end, success));
+    }
+    assert!(events.iter().any(|e| e["name"] == "JsonValue"));
+    assert!(events.iter().any(|e| e["args"]["memo_hit"] == true));
+
+    // Events must nest: an event is either disjoint from or contained in the enclosing events,
+    // and the text span of a successful event contains those of its successful children.
+    const EPSILON: f64 = 0.002;
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0).then(b.1.total_cmp(&a.1)));
+    let mut stack: Vec<(f64, f64, u64, u64, bool)> = Vec::new();
+    for span in spans {
+        while let Some(parent) = stack.last() {
+            if span.1 <= parent.1 + EPSILON {
+                break;
+            }
+            assert!(parent.1 <= span.0 + EPSILON, "{parent:?} overlaps {span:?}");
+            stack.pop();
+        }
+        if let Some(parent) = stack.last() {
+            assert!(parent.2 <= span.2, "{parent:?} does not contain {span:?}");
+            if parent.4 && span.4 {
+                assert!(span.3 <= parent.3, "{parent:?} does not contain {span:?}");
+            }
+        }
+        stack.push(span);
+    }
+}
@@ -0,0 +1,85 @@
+#![allow(dead_code)]
+use std::ops::Range;
+use textparse::{
+    components::{Char, Digit, Items, NonEmpty, While, Whitespace},
+    Parse, Parser, Position, Span,
+};
+
+#[derive(Clone, Span, Parse)]
+#[parse(lexeme)]
+struct Number(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+struct Numbers(Items<Number, Char<','>>);
+
+fn span(start: usize, end: usize) -> Range<Position> {
+    Position::new(start)..Position::new(end)
+}
+
+#[test]
+fn trivia_is_split_at_the_first_newline() {
+    //          0         1
+    //          0123456789012345
+    let text = " 1 ,  2\n  , 3  \n";
+    let mut parser = Parser::new(text).trivia::<Whitespace>();
+    parser.skip_trivia();
+    assert!(parser.parse::<Numbers>().is_some());
+    parser.skip_trivia();
+    assert!(parser.is_eos());
+
+    // A trivia run at the beginning of the text is entirely leading trivia.
+    assert_eq!(parser.leading_trivia(&span(1, 2)), span(0, 1));
+
+    // A trivia run without newlines is entirely trailing trivia.
+    assert_eq!(parser.trailing_trivia(&span(1, 2)), span(2, 3));
+    assert_eq!(parser.leading_trivia(&span(3, 4)), span(3, 3));
+
+    // A trivia run with newlines is split after the first newline.
+    assert_eq!(parser.trailing_trivia(&span(6, 7)), span(7, 8));
+    assert_eq!(parser.leading_trivia(&span(10, 11)), span(8, 10));
+    assert_eq!(parser.trailing_trivia(&span(12, 13)), span(13, 16));
+}
+
+#[test]
+fn items_without_adjacent_trivia_have_empty_trivia() {
+    let text = "1,2";
+    let mut parser = Parser::new(text).trivia::<Whitespace>();
+    assert!(parser.parse::<Numbers>().is_some());
+    assert_eq!(parser.leading_trivia(&span(0, 1)), span(0, 0));
+    assert_eq!(parser.trailing_trivia(&span(0, 1)), span(1, 1));
+    assert_eq!(parser.leading_trivia(&span(2, 3)), span(2, 2));
+    assert_eq!(parser.trailing_trivia(&span(2, 3)), span(3, 3));
+    assert_eq!(parser.trivia_ranges().count(), 0);
+}
+
+#[derive(Clone, Span, Parse)]
+enum List {
+    Closed(ClosedList),
+    Open(Numbers),
+}
+
+#[derive(Clone, Span, Parse)]
+struct ClosedList(Numbers, Char<';'>);
+
+#[test]
+fn trivia_of_abandoned_attempts_is_discarded() {
+    let text = "1  ";
+    let mut parser = Parser::new(text).trivia::<Whitespace>();
+    let numbers = parser.parse::<Numbers>().unwrap();
+    assert_eq!(numbers.end_position(), Position::new(1));
+    assert_eq!(parser.trivia_ranges().count(), 0);
+    assert_eq!(parser.trailing_trivia(&numbers), span(1, 1));
+}
+
+#[test]
+fn trivia_of_memoized_items_is_restored() {
+    // `Numbers` fails to be followed by `;`, and then is reused from the memo by `List::Open`.
+    let text = "1 , 2";
+    let mut parser = Parser::new(text).trivia::<Whitespace>();
+    assert!(matches!(parser.parse::<List>(), Some(List::Open(_))));
+    assert_eq!(
+        parser.trivia_ranges().collect::<Vec<_>>(),
+        [span(1, 2), span(3, 4)]
+    );
+    assert_eq!(parser.trailing_trivia(&span(0, 1)), span(1, 2));
+}
@@ -0,0 +1,6 @@
+use textparse::{components::Char, Parse, Span};
+
+#[derive(Clone, Span, Parse)]
+struct Hyphen(Char<'a'>, #[parse(no-skip)] Char<'b'>);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use textparse::{
+    components::{Digit, While},
+    Parse, Span, SyntaxEq,
+};
+
+// `While` does not keep its contents, so it cannot be compared.
+#[derive(Clone, Span, Parse, SyntaxEq)]
+struct Number(While<Digit>);
+
+fn main() {}
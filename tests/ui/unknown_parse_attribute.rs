@@ -0,0 +1,14 @@
+use textparse::{components::Char, Parse, Span};
+
+#[derive(Clone, Span, Parse)]
+#[parse(lexme)]
+struct Misspelled(Char<'a'>);
+
+#[derive(Clone, Span, Parse)]
+struct Field(Char<'a'>, #[parse(name = "x")] Char<'b'>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = 1)]
+struct NotString(Char<'a'>);
+
+fn main() {}
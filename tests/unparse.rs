@@ -0,0 +1,16 @@
+mod common;
+
+use common::JsonValue;
+use textparse::{parse_str, Unparse};
+
+#[test]
+fn json_round_trip() {
+    for text in [
+        "null",
+        " [1 , {\"key\":null} ]\n",
+        "{\"a\": [\"x y\", 123, []], \"b\": {}}",
+    ] {
+        let value = parse_str::<JsonValue>(text).expect("parse error");
+        assert_eq!(value.unparse_to_string(), text);
+    }
+}
@@ -0,0 +1,114 @@
+use std::any::Any;
+use textparse::{
+    components::{Char, Digit, Either, Items, Maybe, NonEmpty, Text, While},
+    Parse, Parser, Position, Span, Visit, VisitMut, Visitor, VisitorMut,
+};
+
+#[derive(Clone, Span, Parse, Visit, VisitMut)]
+struct Array(Char<'['>, Items<Element, Char<','>>, Char<']'>);
+
+#[derive(Clone, Span, Parse, Visit, VisitMut)]
+struct Element(Either<Number, Either<Box<Array>, Pair>>);
+
+#[derive(Clone, Span, Parse, Visit, VisitMut)]
+struct Pair(Char<'('>, (Number, Maybe<(Char<':'>, Number)>), Char<')'>);
+
+#[derive(Debug, Clone, Span)]
+struct Number {
+    start_position: Position,
+    value: u32,
+    end_position: Position,
+}
+
+impl Parse for Number {
+    fn parse(parser: &mut Parser) -> Option<Self> {
+        let digits = parser.parse::<Text<NonEmpty<While<Digit>>>>()?;
+        Some(Self {
+            start_position: digits.start_position(),
+            value: digits.as_str().parse().ok()?,
+            end_position: digits.end_position(),
+        })
+    }
+}
+
+impl Visit for Number {
+    fn walk<V: Visitor>(&self, _visitor: &mut V) {}
+}
+
+impl VisitMut for Number {
+    fn walk_mut<V: VisitorMut>(&mut self, _visitor: &mut V) {}
+}
+
+fn parse(text: &str) -> Array {
+    textparse::parse_str(text).unwrap()
+}
+
+#[derive(Default)]
+struct NumberCollector {
+    numbers: Vec<u32>,
+    skip_nested_arrays: bool,
+    arrays: usize,
+}
+
+impl Visitor for NumberCollector {
+    fn visit<T: Visit>(&mut self, item: &T) {
+        let item_any = item as &dyn Any;
+        if let Some(number) = item_any.downcast_ref::<Number>() {
+            self.numbers.push(number.value);
+        } else if item_any.is::<Array>() {
+            self.arrays += 1;
+            if self.skip_nested_arrays && self.arrays > 1 {
+                return;
+            }
+        }
+        item.walk(self);
+    }
+}
+
+const TEXT: &str = "[1,[2,(3:4)],(5),[[6]]]";
+
+#[test]
+fn visitor_reaches_nested_items() {
+    let array = parse(TEXT);
+    let mut visitor = NumberCollector::default();
+    visitor.visit(&array);
+    assert_eq!(visitor.numbers, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(visitor.arrays, 4);
+
+    // The children are visited by `walk()`, not including the item itself.
+    let mut visitor = NumberCollector::default();
+    array.walk(&mut visitor);
+    assert_eq!(visitor.arrays, 3);
+}
+
+#[test]
+fn visitor_can_skip_children() {
+    let array = parse(TEXT);
+    let mut visitor = NumberCollector {
+        skip_nested_arrays: true,
+        ..Default::default()
+    };
+    visitor.visit(&array);
+    assert_eq!(visitor.numbers, [1, 5]);
+}
+
+struct Doubler;
+
+impl VisitorMut for Doubler {
+    fn visit_mut<T: VisitMut>(&mut self, item: &mut T) {
+        if let Some(number) = (item as &mut dyn Any).downcast_mut::<Number>() {
+            number.value *= 2;
+        }
+        item.walk_mut(self);
+    }
+}
+
+#[test]
+fn visitor_mut_mutates_nested_items() {
+    let mut array = parse(TEXT);
+    Doubler.visit_mut(&mut array);
+
+    let mut visitor = NumberCollector::default();
+    visitor.visit(&array);
+    assert_eq!(visitor.numbers, [2, 4, 6, 8, 10, 12]);
+}
@@ -3,8 +3,8 @@ use proc_macro_crate::FoundCrate;
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics, Index, Lit,
-    Meta, NestedMeta,
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Field, Fields, GenericParam,
+    Generics, Index, Lit, Meta, NestedMeta,
 };
 
 fn crate_name() -> TokenStream {
@@ -19,6 +19,15 @@ fn crate_name() -> TokenStream {
     }
 }
 
+fn add_trait_bounds(mut generics: Generics, bound: TokenStream) -> Generics {
+    for param in &mut generics.params {
+        if let GenericParam::Type(type_param) = param {
+            type_param.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
+
 #[proc_macro_derive(Span)]
 pub fn derive_span_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let textparse = crate_name();
@@ -125,30 +134,43 @@ pub fn derive_parse_trait(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     let name = input.ident;
     let generics = add_parse_trait_bounds(input.generics);
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-    let parse = generate_parse_fun_body(&input.data);
-    let item_name = if let Some(attrs) = input
-        .attrs
-        .iter()
-        .find(|a| a.path.segments.len() == 1 && a.path.segments[0].ident == "parse")
-    {
-        let Meta::List(meta_list) = attrs.parse_meta().unwrap() else {
-            todo!("{}:{}", module_path!(), line!());
-        };
-        assert_eq!(meta_list.nested.len(), 1);
-
-        let Some(NestedMeta::Meta(Meta::NameValue(name_value))) = meta_list.nested.first() else {
-            todo!("{}:{}", module_path!(), line!());
-        };
-        assert_eq!(name_value.path.segments.len(), 1);
-        assert_eq!(name_value.path.segments[0].ident, "name");
-
-        let Lit::Str(value) = &name_value.lit else {
-            todo!("{}:{}", module_path!(), line!());
-        };
-        quote!(Some(|| #value.to_owned()))
-    } else {
-        quote!(None)
+    let mut item_name = quote!(None);
+    let mut lexeme = false;
+    let metas = match parse_attr_metas(&input.attrs) {
+        Ok(metas) => metas,
+        Err(e) => return e.to_compile_error().into(),
     };
+    for meta in metas {
+        match meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("name") => {
+                let Lit::Str(value) = &name_value.lit else {
+                    return syn::Error::new_spanned(name_value.lit, "expected a string literal")
+                        .to_compile_error()
+                        .into();
+                };
+                item_name = quote!(Some(|| #value.to_owned()));
+            }
+            Meta::Path(path) if path.is_ident("lexeme") => {
+                lexeme = true;
+            }
+            meta => {
+                return syn::Error::new_spanned(meta, "unknown parse attribute")
+                    .to_compile_error()
+                    .into();
+            }
+        }
+    }
+    if let Err(e) = check_field_attrs(&input.data) {
+        return e.to_compile_error().into();
+    }
+    let mut parse = generate_parse_fun_body(&input.data);
+    if lexeme {
+        parse = quote! {
+            parser.lexeme(|parser| {
+                #parse
+            })
+        };
+    }
     let expanded = quote! {
         impl #impl_generics #textparse::Parse for #name #ty_generics #where_clause {
             fn parse(parser: &mut #textparse::Parser) -> Option<Self> {
@@ -163,6 +185,58 @@ pub fn derive_parse_trait(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     proc_macro::TokenStream::from(expanded)
 }
 
+fn parse_attr_metas(attrs: &[Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("parse")) {
+        let Meta::List(meta_list) = attr.parse_meta()? else {
+            return Err(syn::Error::new_spanned(attr, "expected `#[parse(...)]`"));
+        };
+        for nested in meta_list.nested {
+            let NestedMeta::Meta(meta) = nested else {
+                return Err(syn::Error::new_spanned(nested, "unknown parse attribute"));
+            };
+            metas.push(meta);
+        }
+    }
+    Ok(metas)
+}
+
+fn check_field_attrs(data: &Data) -> syn::Result<()> {
+    let Data::Struct(data) = data else {
+        return Ok(());
+    };
+    for field in &data.fields {
+        for meta in parse_attr_metas(&field.attrs)? {
+            if !matches!(&meta, Meta::Path(path) if path.is_ident("no_skip")) {
+                return Err(syn::Error::new_spanned(meta, "unknown parse attribute"));
+            }
+        }
+    }
+    Ok(())
+}
+
+// The attributes are validated by `check_field_attrs()` beforehand.
+fn has_no_skip_attr(field: &Field) -> bool {
+    parse_attr_metas(&field.attrs)
+        .unwrap_or_default()
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident("no_skip")))
+}
+
+fn field_vars(n: usize) -> Vec<Ident> {
+    (0..n)
+        .map(|i| Ident::new(&format!("field{i}"), Span::call_site()))
+        .collect()
+}
+
+fn skip_trivia_before(i: usize, field: &Field) -> TokenStream {
+    if i == 0 || has_no_skip_attr(field) {
+        quote!()
+    } else {
+        quote!(parser.skip_trivia();)
+    }
+}
+
 fn add_parse_trait_bounds(mut generics: Generics) -> Generics {
     let textparse = crate_name();
     for param in &mut generics.params {
@@ -177,23 +251,39 @@ fn generate_parse_fun_body(data: &Data) -> TokenStream {
     match data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => {
-                let parse = fields.named.iter().map(|f| {
-                    let name = &f.ident;
-                    quote_spanned! { f.span() => #name: parser.parse()? }
-                });
+                let vars = field_vars(fields.named.len());
+                let parse = fields
+                    .named
+                    .iter()
+                    .zip(&vars)
+                    .enumerate()
+                    .map(|(i, (f, var))| {
+                        let skip = skip_trivia_before(i, f);
+                        quote_spanned! { f.span() => #skip let #var = parser.parse()?; }
+                    });
+                let names = fields.named.iter().map(|f| &f.ident);
                 quote! {
+                    #(#parse)*
                     Some(Self{
-                        #(#parse ,)*
+                        #(#names: #vars ,)*
                     })
                 }
             }
             Fields::Unnamed(fields) => {
-                let parse = fields.unnamed.iter().map(|f| {
-                    quote_spanned! { f.span() => parser.parse()? }
-                });
+                let vars = field_vars(fields.unnamed.len());
+                let parse = fields
+                    .unnamed
+                    .iter()
+                    .zip(&vars)
+                    .enumerate()
+                    .map(|(i, (f, var))| {
+                        let skip = skip_trivia_before(i, f);
+                        quote_spanned! { f.span() => #skip let #var = parser.parse()?; }
+                    });
                 quote! {
+                    #(#parse)*
                     Some(Self(
-                        #(#parse ,)*
+                        #(#vars ,)*
                     ))
                 }
             }
@@ -219,3 +309,337 @@ fn generate_parse_fun_body(data: &Data) -> TokenStream {
         Data::Union(_) => unimplemented!(),
     }
 }
+
+#[proc_macro_derive(Dump)]
+pub fn derive_dump_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::Dump));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let dump = generate_dump_fun_body(&input.data);
+    let expanded = quote! {
+        impl #impl_generics #textparse::Dump for #name #ty_generics #where_clause {
+            fn dump(&self, dumper: &mut #textparse::Dumper) {
+                dumper.node(self, |dumper| {
+                    #dump
+                });
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+fn generate_dump_fun_body(data: &Data) -> TokenStream {
+    let textparse = crate_name();
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let dump = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote_spanned! { f.span() => #textparse::Dump::dump(&self.#name, dumper); }
+                });
+                quote! { #(#dump)* }
+            }
+            Fields::Unnamed(fields) => {
+                let dump = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let i = Index::from(i);
+                    quote_spanned! { f.span() => #textparse::Dump::dump(&self.#i, dumper); }
+                });
+                quote! { #(#dump)* }
+            }
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                if let Fields::Unnamed(fields) = &variant.fields {
+                    assert_eq!(fields.unnamed.len(), 1);
+                } else {
+                    unimplemented!();
+                }
+                quote_spanned! { variant.span() => Self::#name(x) => #textparse::Dump::dump(x, dumper), }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    }
+}
+
+#[proc_macro_derive(Unparse)]
+pub fn derive_unparse_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::Unparse));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let unparse = generate_unparse_fun_body(&input.data);
+    let expanded = quote! {
+        impl #impl_generics #textparse::Unparse for #name #ty_generics #where_clause {
+            fn unparse(&self, writer: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                #unparse
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+fn generate_unparse_fun_body(data: &Data) -> TokenStream {
+    let textparse = crate_name();
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let unparse = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote_spanned! { f.span() => #textparse::Unparse::unparse(&self.#name, writer)?; }
+                });
+                quote! {
+                    #(#unparse)*
+                    Ok(())
+                }
+            }
+            Fields::Unnamed(fields) => {
+                let unparse = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let i = Index::from(i);
+                    quote_spanned! { f.span() => #textparse::Unparse::unparse(&self.#i, writer)?; }
+                });
+                quote! {
+                    #(#unparse)*
+                    Ok(())
+                }
+            }
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                if let Fields::Unnamed(fields) = &variant.fields {
+                    assert_eq!(fields.unnamed.len(), 1);
+                } else {
+                    unimplemented!();
+                }
+                quote_spanned! { variant.span() => Self::#name(x) => #textparse::Unparse::unparse(x, writer), }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    }
+}
+
+#[proc_macro_derive(Visit)]
+pub fn derive_visit_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::Visit));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let walk = generate_walk_fun_body(&input.data, false);
+    let expanded = quote! {
+        impl #impl_generics #textparse::Visit for #name #ty_generics #where_clause {
+            fn walk<V: #textparse::Visitor>(&self, visitor: &mut V) {
+                #walk
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(VisitMut)]
+pub fn derive_visit_mut_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::VisitMut));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let walk = generate_walk_fun_body(&input.data, true);
+    let expanded = quote! {
+        impl #impl_generics #textparse::VisitMut for #name #ty_generics #where_clause {
+            fn walk_mut<V: #textparse::VisitorMut>(&mut self, visitor: &mut V) {
+                #walk
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+fn generate_walk_fun_body(data: &Data, mutable: bool) -> TokenStream {
+    let textparse = crate_name();
+    let visit = if mutable {
+        quote!(#textparse::VisitorMut::visit_mut)
+    } else {
+        quote!(#textparse::Visitor::visit)
+    };
+    let reference = if mutable { quote!(&mut) } else { quote!(&) };
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let visits = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote_spanned! { f.span() => #visit(visitor, #reference self.#name); }
+                });
+                quote! { #(#visits)* }
+            }
+            Fields::Unnamed(fields) => {
+                let visits = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let i = Index::from(i);
+                    quote_spanned! { f.span() => #visit(visitor, #reference self.#i); }
+                });
+                quote! { #(#visits)* }
+            }
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                if let Fields::Unnamed(fields) = &variant.fields {
+                    assert_eq!(fields.unnamed.len(), 1);
+                } else {
+                    unimplemented!();
+                }
+                quote_spanned! { variant.span() => Self::#name(x) => #visit(visitor, x), }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    }
+}
+
+#[proc_macro_derive(SyntaxEq)]
+pub fn derive_syntax_eq_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::SyntaxEq));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let syntax_eq = generate_syntax_eq_fun_body(&input.data);
+    let expanded = quote! {
+        impl #impl_generics #textparse::SyntaxEq for #name #ty_generics #where_clause {
+            fn syntax_eq(&self, other: &Self) -> bool {
+                #syntax_eq
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(SyntaxHash)]
+pub fn derive_syntax_hash_trait(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let textparse = crate_name();
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let generics = add_trait_bounds(input.generics, quote!(#textparse::SyntaxHash));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let syntax_hash = generate_syntax_hash_fun_body(&input.data);
+    let expanded = quote! {
+        impl #impl_generics #textparse::SyntaxHash for #name #ty_generics #where_clause {
+            fn syntax_hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                #syntax_hash
+            }
+        }
+    };
+    proc_macro::TokenStream::from(expanded)
+}
+
+fn generate_syntax_eq_fun_body(data: &Data) -> TokenStream {
+    let textparse = crate_name();
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let eqs = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote_spanned! { f.span() => #textparse::SyntaxEq::syntax_eq(&self.#name, &other.#name) }
+                });
+                join_syntax_eqs(eqs)
+            }
+            Fields::Unnamed(fields) => {
+                let eqs = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let i = Index::from(i);
+                    quote_spanned! { f.span() => #textparse::SyntaxEq::syntax_eq(&self.#i, &other.#i) }
+                });
+                join_syntax_eqs(eqs)
+            }
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                if let Fields::Unnamed(fields) = &variant.fields {
+                    assert_eq!(fields.unnamed.len(), 1);
+                } else {
+                    unimplemented!();
+                }
+                quote_spanned! { variant.span() => (Self::#name(x), Self::#name(y)) => #textparse::SyntaxEq::syntax_eq(x, y), }
+            });
+            quote! {
+                #[allow(unreachable_patterns)]
+                match (self, other) {
+                    #(#arms)*
+                    _ => false,
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    }
+}
+
+fn join_syntax_eqs(eqs: impl Iterator<Item = TokenStream>) -> TokenStream {
+    let eqs = eqs.collect::<Vec<_>>();
+    if eqs.is_empty() {
+        quote! { true }
+    } else {
+        quote! { #(#eqs)&&* }
+    }
+}
+
+fn generate_syntax_hash_fun_body(data: &Data) -> TokenStream {
+    let textparse = crate_name();
+    match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let hashes = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote_spanned! { f.span() => #textparse::SyntaxHash::syntax_hash(&self.#name, state); }
+                });
+                quote! { #(#hashes)* }
+            }
+            Fields::Unnamed(fields) => {
+                let hashes = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let i = Index::from(i);
+                    quote_spanned! { f.span() => #textparse::SyntaxHash::syntax_hash(&self.#i, state); }
+                });
+                quote! { #(#hashes)* }
+            }
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let name = &variant.ident;
+                if let Fields::Unnamed(fields) = &variant.fields {
+                    assert_eq!(fields.unnamed.len(), 1);
+                } else {
+                    unimplemented!();
+                }
+                quote_spanned! { variant.span() => Self::#name(x) => #textparse::SyntaxHash::syntax_hash(x, state), }
+            });
+            quote! {
+                std::hash::Hash::hash(&std::mem::discriminant(self), state);
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => unimplemented!(),
+    }
+}
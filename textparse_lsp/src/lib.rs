@@ -0,0 +1,410 @@
+//! Language Server Protocol adapter for [textparse](https://github.com/sile/textparse) grammars.
+//!
+//! [`LanguageServer`] speaks LSP for a root [`Parse`] type and provides
+//! diagnostics, semantic tokens, document symbols and completion.
+#![warn(missing_docs)]
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, DocumentSymbolRequest, Request as RequestTrait, SemanticTokensFullRequest,
+};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+    CompletionTextEdit, Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbol, DocumentSymbolParams,
+    DocumentSymbolResponse, OneOf, PublishDiagnosticsParams, Range, SemanticToken,
+    SemanticTokenType, SemanticTokens, SemanticTokensFullOptions, SemanticTokensLegend,
+    SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, SymbolKind, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url,
+};
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::error::Error;
+use std::marker::PhantomData;
+use textparse::{
+    components::Eos, highlight::Highlighter, LineIndex, Parse, Parser, Position, Span,
+};
+
+/// Default value of [`LanguageServer::max_depth()`].
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
+/// Error type of [`LanguageServer`].
+pub type LspError = Box<dyn Error + Send + Sync>;
+
+/// Language server for documents of which root item type is `T`.
+#[derive(Debug)]
+pub struct LanguageServer<T> {
+    highlighter: Highlighter,
+    token_types: Vec<SemanticTokenType>,
+    symbol_kinds: HashMap<TypeId, SymbolKind>,
+    documents: HashMap<Url, String>,
+    max_depth: usize,
+    _root: PhantomData<fn() -> T>,
+}
+
+impl<T: Parse> LanguageServer<T> {
+    /// Makes a new [`LanguageServer`] instance.
+    pub fn new() -> Self {
+        Self {
+            highlighter: Highlighter::new(),
+            token_types: Vec::new(),
+            symbol_kinds: HashMap::new(),
+            documents: HashMap::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            _root: PhantomData,
+        }
+    }
+
+    /// Reports the items of `U` as semantic tokens of `token_type`.
+    pub fn semantic_token<U: Parse>(mut self, token_type: SemanticTokenType) -> Self {
+        self.highlighter = self.highlighter.class::<U>(token_type.as_str());
+        if !self.token_types.contains(&token_type) {
+            self.token_types.push(token_type);
+        }
+        self
+    }
+
+    /// Reports the items of `U` as document symbols of `kind`.
+    pub fn document_symbol<U: Parse>(mut self, kind: SymbolKind) -> Self {
+        self.symbol_kinds.insert(TypeId::of::<U>(), kind);
+        self
+    }
+
+    /// Sets the maximum nesting depth of rule invocations (cf. [`Parser::max_depth()`]).
+    ///
+    /// Documents nested deeper than this are reported as errors (and get no completion candidates)
+    /// instead of overflowing the stack.
+    ///
+    /// The default value is [`DEFAULT_MAX_DEPTH`].
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns the server capabilities.
+    pub fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: SemanticTokensLegend {
+                        token_types: self.token_types.clone(),
+                        token_modifiers: Vec::new(),
+                    },
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    ..Default::default()
+                }),
+            ),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions::default()),
+            ..Default::default()
+        }
+    }
+
+    /// Runs the server over the standard input and output until the client shuts it down.
+    pub fn run_stdio(self) -> Result<(), LspError> {
+        let (connection, io_threads) = Connection::stdio();
+        self.run(connection)?;
+        io_threads.join()?;
+        Ok(())
+    }
+
+    /// Runs the server over `connection` until the client shuts it down.
+    ///
+    /// [`Connection::memory()`] can be used to run the server in-process (e.g., for testing).
+    pub fn run(mut self, connection: Connection) -> Result<(), LspError> {
+        connection.initialize(serde_json::to_value(self.capabilities())?)?;
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    if let Some(notification) = self.handle_notification(notification)? {
+                        connection
+                            .sender
+                            .send(Message::Notification(notification))?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            SemanticTokensFullRequest::METHOD => request
+                .extract::<SemanticTokensParams>(SemanticTokensFullRequest::METHOD)
+                .map_err(|e| e.to_string())
+                .and_then(|(_, params)| {
+                    let result = self.semantic_tokens(&params.text_document.uri);
+                    serde_json::to_value(result).map_err(|e| e.to_string())
+                }),
+            DocumentSymbolRequest::METHOD => request
+                .extract::<DocumentSymbolParams>(DocumentSymbolRequest::METHOD)
+                .map_err(|e| e.to_string())
+                .and_then(|(_, params)| {
+                    let result = self.document_symbols(&params.text_document.uri);
+                    serde_json::to_value(result).map_err(|e| e.to_string())
+                }),
+            Completion::METHOD => request
+                .extract::<CompletionParams>(Completion::METHOD)
+                .map_err(|e| e.to_string())
+                .and_then(|(_, params)| {
+                    let position = params.text_document_position;
+                    let result = self.completion(&position.text_document.uri, position.position);
+                    serde_json::to_value(result).map_err(|e| e.to_string())
+                }),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported method: {method}"),
+                );
+            }
+        };
+        match result {
+            Ok(value) => Response {
+                id,
+                result: Some(value),
+                error: None,
+            },
+            Err(e) => Response::new_err(id, ErrorCode::InvalidParams as i32, e),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Option<Notification>, LspError> {
+        let (uri, version) = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                (document.uri, Some(document.version))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(document.uri.clone(), change.text);
+                }
+                (document.uri, Some(document.version))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                (params.text_document.uri, None)
+            }
+            _ => return Ok(None),
+        };
+
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|text| self.diagnostics(text))
+            .unwrap_or_default();
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version,
+        };
+        Ok(Some(Notification::new(
+            PublishDiagnostics::METHOD.to_owned(),
+            params,
+        )))
+    }
+
+    fn semantic_tokens(&self, uri: &Url) -> Option<SemanticTokensResult> {
+        let text = self.documents.get(uri)?;
+        let mut parser = self.parser(text);
+        let _ = parser.parse::<(T, Eos)>();
+        let lines = LineIndex::new(text);
+
+        let mut data = Vec::new();
+        let (mut prev_line, mut prev_start) = (0, 0);
+        for highlight in self.highlighter.highlight(&parser) {
+            let Some(token_type) = self
+                .token_types
+                .iter()
+                .position(|t| t.as_str() == highlight.class())
+            else {
+                continue;
+            };
+
+            // Tokens must not span multiple lines.
+            let mut offset = highlight.start_position().get();
+            for segment in highlight.text(text).split_inclusive('\n') {
+                let content = segment.trim_end_matches(['\n', '\r']);
+                let start = lsp_position(&lines, Position::new(offset));
+                offset += segment.len();
+                if content.is_empty() {
+                    continue;
+                }
+                let delta_line = start.line - prev_line;
+                let delta_start = if delta_line == 0 {
+                    start.character - prev_start
+                } else {
+                    start.character
+                };
+                data.push(SemanticToken {
+                    delta_line,
+                    delta_start,
+                    length: content.encode_utf16().count() as u32,
+                    token_type: token_type as u32,
+                    token_modifiers_bitset: 0,
+                });
+                (prev_line, prev_start) = (start.line, start.character);
+            }
+        }
+        Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        }))
+    }
+
+    fn document_symbols(&self, uri: &Url) -> Option<DocumentSymbolResponse> {
+        let text = self.documents.get(uri)?;
+        let mut parser = self.parser(text);
+        let _ = parser.parse::<(T, Eos)>();
+        let lines = LineIndex::new(text);
+
+        // The items are sorted by start position, and outer items come first.
+        let items = parser.result_items().into_iter().filter_map(|item| {
+            let kind = *self.symbol_kinds.get(&item.rule().type_id())?;
+            (!item.is_empty()).then_some((item, kind))
+        });
+
+        // Builds the symbol tree based on the span containment.
+        let mut roots = Vec::new();
+        let mut stack: Vec<(Position, DocumentSymbol)> = Vec::new();
+        for (item, kind) in items {
+            while stack
+                .last()
+                .is_some_and(|(parent_end, _)| *parent_end <= item.start_position())
+            {
+                close_symbol(&mut stack, &mut roots);
+            }
+            let span = item.start_position()..item.end_position();
+            let range = Range::new(
+                lsp_position(&lines, span.start),
+                lsp_position(&lines, span.end),
+            );
+            #[allow(deprecated)]
+            let symbol = DocumentSymbol {
+                name: symbol_name(span.text(text)),
+                detail: Some(
+                    item.rule()
+                        .name()
+                        .unwrap_or_else(|| item.rule().short_type_name()),
+                ),
+                kind,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range: range,
+                children: None,
+            };
+            stack.push((span.end, symbol));
+        }
+        while !stack.is_empty() {
+            close_symbol(&mut stack, &mut roots);
+        }
+        Some(DocumentSymbolResponse::Nested(roots))
+    }
+
+    fn parser<'a>(&self, text: &'a str) -> Parser<'a> {
+        Parser::new(text).max_depth(self.max_depth)
+    }
+
+    fn diagnostics(&self, text: &str) -> Vec<Diagnostic> {
+        let mut parser = self.parser(text);
+        if parser.parse::<(T, Eos)>().is_some() {
+            return Vec::new();
+        }
+        let error = parser.into_parse_error();
+        let position = lsp_position(&LineIndex::new(text), error.position());
+        let message = error.to_string().lines().next().unwrap_or("").to_owned();
+        vec![Diagnostic {
+            range: Range::new(position, position),
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("textparse".to_owned()),
+            message,
+            ..Default::default()
+        }]
+    }
+
+    fn completion(&self, uri: &Url, position: lsp_types::Position) -> Option<CompletionResponse> {
+        let text = self.documents.get(uri)?;
+        let lines = LineIndex::new(text);
+        let cursor = lines.position_from_utf16(position.line as usize, position.character as usize);
+        let completions = textparse::completions::<T>(self.parser(text), cursor).ok()?;
+        let range = Range::new(lsp_position(&lines, completions.position()), position);
+        let items = completions
+            .items()
+            .iter()
+            .filter_map(|rule| {
+                let literal = rule.literal()?;
+                let kind = if literal.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    CompletionItemKind::KEYWORD
+                } else {
+                    CompletionItemKind::OPERATOR
+                };
+                Some(CompletionItem {
+                    label: literal.clone(),
+                    kind: Some(kind),
+                    detail: rule.name(),
+                    text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, literal))),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+}
+
+impl<T: Parse> Default for LanguageServer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn close_symbol(stack: &mut Vec<(Position, DocumentSymbol)>, roots: &mut Vec<DocumentSymbol>) {
+    let Some((_, symbol)) = stack.pop() else {
+        return;
+    };
+    if let Some((_, parent)) = stack.last_mut() {
+        parent.children.get_or_insert_with(Vec::new).push(symbol);
+    } else {
+        roots.push(symbol);
+    }
+}
+
+fn symbol_name(text: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let line = text.trim().lines().next().unwrap_or("");
+    if line.chars().count() > MAX_CHARS {
+        format!("{}...", line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        line.to_owned()
+    }
+}
+
+fn lsp_position(lines: &LineIndex, position: Position) -> lsp_types::Position {
+    let (line, character) = lines.utf16_line_and_column(position);
+    lsp_types::Position::new(line as u32, character as u32)
+}
@@ -0,0 +1,212 @@
+#![allow(dead_code)]
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    CompletionResponse, DocumentSymbolResponse, PublishDiagnosticsParams, SemanticTokenType,
+    SemanticTokensResult, SymbolKind,
+};
+use serde_json::{json, Value};
+use std::thread::JoinHandle;
+use textparse::{
+    components::{Char, Digit, Items, NonEmpty, Str, While, Whitespace},
+    Parse, Span,
+};
+use textparse_lsp::{LanguageServer, LspError};
+
+#[derive(Clone, Span, Parse)]
+struct Element(WithoutWhitespaces<ValueInner>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "a value")]
+enum ValueInner {
+    Null(Null),
+    Number(Number),
+    Array(Array),
+}
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "null")]
+struct Null(Str<'n', 'u', 'l', 'l'>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "a number")]
+struct Number(NonEmpty<While<Digit>>);
+
+#[derive(Clone, Span, Parse)]
+#[parse(name = "an array")]
+struct Array(Char<'['>, Items<Element, Char<','>>, Char<']'>);
+
+#[derive(Clone, Span, Parse)]
+struct WithoutWhitespaces<T>(While<Whitespace>, T, While<Whitespace>);
+
+const URI: &str = "file:///test.txt";
+
+struct Client {
+    connection: Connection,
+    server: JoinHandle<Result<(), LspError>>,
+    next_id: i32,
+}
+
+impl Client {
+    fn start() -> Self {
+        let (server_connection, connection) = Connection::memory();
+        let server = std::thread::spawn(move || {
+            LanguageServer::<Element>::new()
+                .semantic_token::<Number>(SemanticTokenType::NUMBER)
+                .semantic_token::<Null>(SemanticTokenType::KEYWORD)
+                .document_symbol::<Array>(SymbolKind::ARRAY)
+                .run(server_connection)
+        });
+        let mut this = Self {
+            connection,
+            server,
+            next_id: 0,
+        };
+        let result = this.request("initialize", json!({"capabilities": {}}));
+        assert!(result["capabilities"]["semanticTokensProvider"].is_object());
+        this.notify("initialized", json!({}));
+        this
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), method.to_owned(), params);
+        self.connection
+            .sender
+            .send(Message::Request(request))
+            .unwrap();
+        match self.connection.receiver.recv().unwrap() {
+            Message::Response(Response {
+                id: response_id,
+                result,
+                error: None,
+            }) if response_id == id => result.unwrap_or(Value::Null),
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+
+    fn notify(&self, method: &str, params: Value) {
+        let notification = Notification::new(method.to_owned(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))
+            .unwrap();
+    }
+
+    fn open(&self, text: &str) -> PublishDiagnosticsParams {
+        self.notify(
+            "textDocument/didOpen",
+            json!({"textDocument": {"uri": URI, "languageId": "test", "version": 1, "text": text}}),
+        );
+        match self.connection.receiver.recv().unwrap() {
+            Message::Notification(n) if n.method == "textDocument/publishDiagnostics" => {
+                serde_json::from_value(n.params).unwrap()
+            }
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+
+    fn shutdown(mut self) {
+        assert_eq!(self.request("shutdown", Value::Null), Value::Null);
+        self.notify("exit", Value::Null);
+        self.server.join().unwrap().unwrap();
+    }
+}
+
+#[test]
+fn language_server_features() {
+    let mut client = Client::start();
+
+    // Diagnostics.
+    let diagnostics = client.open("[1, nul]").diagnostics;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start, lsp_types::Position::new(0, 4));
+
+    let diagnostics = client.open("[1,\n [null, 23]]").diagnostics;
+    assert!(diagnostics.is_empty());
+
+    // Semantic tokens (the legend order is NUMBER, KEYWORD).
+    let result = client.request(
+        "textDocument/semanticTokens/full",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    let SemanticTokensResult::Tokens(tokens) = serde_json::from_value(result).unwrap() else {
+        panic!();
+    };
+    let tokens = tokens
+        .data
+        .iter()
+        .map(|t| (t.delta_line, t.delta_start, t.length, t.token_type))
+        .collect::<Vec<_>>();
+    assert_eq!(tokens, [(0, 1, 1, 0), (1, 2, 4, 1), (0, 6, 2, 0)]);
+
+    // Document symbols.
+    let result = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    let DocumentSymbolResponse::Nested(symbols) = serde_json::from_value(result).unwrap() else {
+        panic!();
+    };
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "[1,");
+    let children = symbols[0].children.as_ref().unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].name, "[null, 23]");
+    assert_eq!(children[0].range.start, lsp_types::Position::new(1, 1));
+    assert!(children[0].children.is_none());
+
+    // Completion.
+    client.open("[1, nu");
+    let result = client.request(
+        "textDocument/completion",
+        json!({"textDocument": {"uri": URI}, "position": {"line": 0, "character": 6}}),
+    );
+    let CompletionResponse::Array(items) = serde_json::from_value(result).unwrap() else {
+        panic!();
+    };
+    let null = items.iter().find(|item| item.label == "null").unwrap();
+    assert_eq!(null.detail.as_deref(), Some("null"));
+
+    client.shutdown();
+}
+
+#[test]
+fn abandoned_alternatives_are_not_reported() {
+    let mut client = Client::start();
+
+    // `1` is parsed as an item of `Array` that fails at `x`.
+    client.open("[1 x");
+    let result = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    assert_eq!(result, json!([]));
+
+    let result = client.request(
+        "textDocument/semanticTokens/full",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    let SemanticTokensResult::Tokens(tokens) = serde_json::from_value(result).unwrap() else {
+        panic!();
+    };
+    assert!(tokens.data.is_empty());
+
+    client.shutdown();
+}
+
+#[test]
+fn deeply_nested_document_is_reported_as_error() {
+    let mut client = Client::start();
+    let text = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    let diagnostics = client.open(&text).diagnostics;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "nesting too deep");
+
+    let result = client.request(
+        "textDocument/completion",
+        json!({"textDocument": {"uri": URI}, "position": {"line": 0, "character": 100_000}}),
+    );
+    assert_eq!(result, Value::Null);
+    client.shutdown();
+}